@@ -0,0 +1,458 @@
+//! Derive macros for `godot-binary-serialization`, these are used through the `derive` feature of
+//! that crate rather than depended on directly
+//!
+//! `#[derive(GodotVariant)]` makes a struct convert to and from a variant, `#[derive(ToVariant)]`
+//! and `#[derive(FromVariant)]` only convert one way
+//!
+//! Structs with named fields are turned into a dictionary with a string key for each field, tuple
+//! structs and structs marked with `#[godot(tuple)]` are turned into an array of their fields in
+//! order. Fields can be changed with these attributes
+//!
+//! - `#[godot(rename = "name")]` uses a different key for the field
+//! - `#[godot(skip)]` leaves the field out, it is filled with its default when decoding
+//! - `#[godot(int = 32)]` or `#[godot(int = 64)]` sets the width of an integer field
+//! - `#[godot(default)]` or `#[godot(default = "path")]` fills in a field that is missing when
+//!   decoding, either with its default or by calling the function at the path
+//!
+//! A missing field without a default is read from null, so [Option] fields become None and other
+//! fields fail to decode
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, ExprPath, Fields, Generics, Index,
+    LitInt, LitStr, Member,
+};
+
+/// Implements `ToVariant`, `FromVariant` and `TryFrom<&dyn GodotVariant>` for a struct, so it can
+/// be encoded and decoded like a variant. This is the same as deriving both `ToVariant` and
+/// `FromVariant`
+#[proc_macro_derive(GodotVariant, attributes(godot))]
+pub fn derive_godot_variant(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    Container::parse(&input)
+        .map(|container| {
+            let write = container.write_variant();
+            let read = container.read_variant();
+
+            quote!(#write #read)
+        })
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `ToVariant` for a struct
+#[proc_macro_derive(ToVariant, attributes(godot))]
+pub fn derive_to_variant(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    Container::parse(&input)
+        .map(|container| container.write_variant())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `FromVariant` and `TryFrom<&dyn GodotVariant>` for a struct
+#[proc_macro_derive(FromVariant, attributes(godot))]
+pub fn derive_from_variant(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    Container::parse(&input)
+        .map(|container| container.read_variant())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The path to the main crate in generated code
+fn krate() -> TokenStream2 {
+    quote!(::godot_binary_serialization)
+}
+
+/// How a missing field is filled in when decoding
+enum FieldDefault {
+    /// The field is read from null, so only types such as [Option] can be missing
+    None,
+    /// The field is filled with `Default::default()`
+    Default,
+    /// The field is filled by calling a function
+    Path(ExprPath),
+}
+
+/// The width an integer field is sent with
+#[derive(Clone, Copy)]
+enum IntWidth {
+    Bit32,
+    Bit64,
+}
+
+struct Field {
+    member: Member,
+    /// The dictionary key, or the index as a string for arrays
+    key: String,
+    skip: bool,
+    int: Option<IntWidth>,
+    default: FieldDefault,
+}
+
+impl Field {
+    fn parse(index: usize, field: &syn::Field) -> syn::Result<Self> {
+        let (member, key) = match &field.ident {
+            Some(ident) => {
+                let key = ident.to_string();
+                let key = key.strip_prefix("r#").unwrap_or(&key).to_owned();
+
+                (Member::Named(ident.clone()), key)
+            }
+            None => (Member::Unnamed(Index::from(index)), index.to_string()),
+        };
+
+        let mut parsed = Self {
+            member,
+            key,
+            skip: false,
+            int: None,
+            default: FieldDefault::None,
+        };
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("godot"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parsed.key = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("skip") {
+                    parsed.skip = true;
+                } else if meta.path.is_ident("int") {
+                    let width = meta.value()?.parse::<LitInt>()?;
+
+                    parsed.int = Some(match width.base10_parse::<u8>()? {
+                        32 => IntWidth::Bit32,
+                        64 => IntWidth::Bit64,
+                        _ => return Err(Error::new_spanned(width, "expected 32 or 64")),
+                    });
+                } else if meta.path.is_ident("default") {
+                    parsed.default = if meta.input.peek(syn::Token![=]) {
+                        FieldDefault::Path(meta.value()?.parse::<LitStr>()?.parse()?)
+                    } else {
+                        FieldDefault::Default
+                    };
+                } else {
+                    return Err(meta.error("unknown godot field attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+
+    fn width(&self) -> Option<TokenStream2> {
+        let krate = krate();
+
+        self.int.map(|width| match width {
+            IntWidth::Bit32 => quote!(#krate::types::convert::IntWidth::Bit32),
+            IntWidth::Bit64 => quote!(#krate::types::convert::IntWidth::Bit64),
+        })
+    }
+
+    /// An expression that turns the field into a variant
+    fn write_variant(&self) -> TokenStream2 {
+        let krate = krate();
+        let member = &self.member;
+        let key = &self.key;
+
+        let variant = match self.width() {
+            Some(width) => quote! {
+                #krate::types::convert::IntVariant::to_int_variant(&self.#member, #width)
+            },
+            None => quote! {
+                #krate::types::convert::ToVariant::to_variant(&self.#member)
+            },
+        };
+
+        quote!(#variant.map_err(|error| error.in_field(#key))?)
+    }
+
+    /// An expression that reads the field out of a variant, giving a result
+    fn read_result(&self, variant: TokenStream2) -> TokenStream2 {
+        let krate = krate();
+
+        match self.width() {
+            Some(width) => quote! {
+                #krate::types::convert::IntVariant::from_int_variant(#variant, #width)
+            },
+            None => quote! {
+                #krate::types::convert::FromVariant::from_variant(#variant)
+            },
+        }
+    }
+
+    /// An expression that reads the field out of `value`
+    fn read_variant(&self) -> TokenStream2 {
+        let key = &self.key;
+        let value = self.read_result(quote!(value));
+
+        quote!(#value.map_err(|error| error.in_field(#key))?)
+    }
+
+    /// An expression for the field when it is missing from the variant. Without a default it is
+    /// read from null like `get_as` does, and fails with the error if the field can't be null
+    fn missing(&self, error: TokenStream2) -> TokenStream2 {
+        let krate = krate();
+
+        match &self.default {
+            FieldDefault::None => {
+                let null = self.read_result(quote!(&#krate::types::primitive::GodotNull));
+                quote!(#null.map_err(|_| #error)?)
+            }
+            FieldDefault::Default => quote!(::core::default::Default::default()),
+            FieldDefault::Path(path) => quote!(#path()),
+        }
+    }
+}
+
+struct Container<'a> {
+    input: &'a DeriveInput,
+    /// Whether the struct is sent as an array instead of a dictionary
+    tuple: bool,
+    /// Whether the struct is built with braces
+    named: bool,
+    fields: Vec<Field>,
+}
+
+impl<'a> Container<'a> {
+    fn parse(input: &'a DeriveInput) -> syn::Result<Self> {
+        let fields = match &input.data {
+            Data::Struct(data) => &data.fields,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "variants can only be derived for structs",
+                ))
+            }
+        };
+
+        let mut tuple = !matches!(fields, Fields::Named(_));
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("godot"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tuple") {
+                    tuple = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown godot container attribute"))
+                }
+            })?;
+        }
+
+        Ok(Self {
+            input,
+            tuple,
+            named: matches!(fields, Fields::Named(_)),
+            fields: fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| Field::parse(index, field))
+                .collect::<syn::Result<_>>()?,
+        })
+    }
+
+    /// The generics of the struct with a bound on every type parameter
+    fn bounded_generics(&self, bound: TokenStream2) -> Generics {
+        let mut generics = self.input.generics.clone();
+        for param in generics.type_params_mut() {
+            param.bounds.push(parse_quote!(#bound));
+        }
+
+        generics
+    }
+
+    fn sent_fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter().filter(|field| !field.skip)
+    }
+
+    fn write_variant(&self) -> TokenStream2 {
+        let krate = krate();
+        let ident = &self.input.ident;
+        let generics = self.bounded_generics(quote!(#krate::types::convert::ToVariant));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let body = if self.tuple {
+            let values = self.sent_fields().map(Field::write_variant);
+
+            quote! {
+                let values = ::std::vec![#(#values),*];
+
+                Ok(::std::boxed::Box::new(
+                    #krate::types::structures::GodotArray::new_from_vec(values),
+                ))
+            }
+        } else {
+            let inserts = self.sent_fields().map(|field| {
+                let key = &field.key;
+                let value = field.write_variant();
+
+                quote! {
+                    dictionary.map.insert(
+                        ::std::boxed::Box::new(#krate::types::primitive::GodotString::new(#key)),
+                        #value,
+                    );
+                }
+            });
+
+            quote! {
+                let mut dictionary = #krate::types::structures::GodotDictionary::new();
+                #(#inserts)*
+
+                Ok(::std::boxed::Box::new(dictionary))
+            }
+        };
+
+        quote! {
+            impl #impl_generics #krate::types::convert::ToVariant for #ident #ty_generics
+                #where_clause
+            {
+                fn to_variant(
+                    &self,
+                ) -> ::core::result::Result<
+                    ::std::boxed::Box<dyn #krate::types::variant::GodotVariant>,
+                    #krate::error::ConvertError,
+                > {
+                    #body
+                }
+            }
+        }
+    }
+
+    fn read_variant(&self) -> TokenStream2 {
+        let krate = krate();
+        let ident = &self.input.ident;
+        let generics = self.bounded_generics(quote!(#krate::types::convert::FromVariant));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let (read, values) = if self.tuple {
+            let expected = self.sent_fields().count();
+            let mut index = 0usize;
+            let values = self
+                .fields
+                .iter()
+                .map(|field| {
+                    if field.skip {
+                        return quote!(::core::default::Default::default());
+                    }
+
+                    let value = field.read_variant();
+                    let missing = field.missing(quote! {
+                        #krate::error::ConvertError::InvalidLength {
+                            expected: #expected,
+                            found: values.len(),
+                        }
+                    });
+                    let current = index;
+                    index += 1;
+
+                    quote! {
+                        match values.get(#current) {
+                            Some(value) => {
+                                let value = &**value;
+                                #value
+                            }
+                            None => #missing,
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let read = quote! {
+                let values = #krate::types::convert::array_elements(variant)?;
+            };
+
+            (read, values)
+        } else {
+            let values = self
+                .fields
+                .iter()
+                .map(|field| {
+                    if field.skip {
+                        return quote!(::core::default::Default::default());
+                    }
+
+                    let key = &field.key;
+                    let value = field.read_variant();
+                    let missing = field.missing(quote! {
+                        #krate::error::ConvertError::MissingField(#key.to_owned())
+                    });
+
+                    quote! {
+                        match dictionary.get_by_name(#key) {
+                            Some(value) => #value,
+                            None => #missing,
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let read = quote! {
+                let dictionary = #krate::types::variant::AsVariant::as_var::<
+                    #krate::types::structures::GodotDictionary,
+                >(variant)
+                .ok_or_else(|| #krate::error::ConvertError::unexpected("a dictionary", variant))?;
+            };
+
+            (read, values)
+        };
+
+        let construct = if self.named {
+            let members = self
+                .fields
+                .iter()
+                .map(|field| field.member.to_token_stream());
+            quote!(Self { #(#members: #values),* })
+        } else if self.fields.is_empty() {
+            quote!(Self {})
+        } else {
+            quote!(Self(#(#values),*))
+        };
+
+        // The lifetime of the variant in TryFrom, named so it can't clash with the struct
+        let mut try_generics = generics.clone();
+        try_generics.params.insert(0, parse_quote!('__variant));
+        let (try_impl_generics, _, _) = try_generics.split_for_impl();
+
+        quote! {
+            impl #impl_generics #krate::types::convert::FromVariant for #ident #ty_generics
+                #where_clause
+            {
+                #[allow(unused_variables)]
+                fn from_variant(
+                    variant: &dyn #krate::types::variant::GodotVariant,
+                ) -> ::core::result::Result<Self, #krate::error::ConvertError> {
+                    #read
+
+                    Ok(#construct)
+                }
+            }
+
+            impl #try_impl_generics ::core::convert::TryFrom<
+                &'__variant dyn #krate::types::variant::GodotVariant,
+            > for #ident #ty_generics #where_clause
+            {
+                type Error = #krate::error::ConvertError;
+
+                fn try_from(
+                    variant: &'__variant dyn #krate::types::variant::GodotVariant,
+                ) -> ::core::result::Result<Self, Self::Error> {
+                    <Self as #krate::types::convert::FromVariant>::from_variant(variant)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Every public decode entry point in one table, shared by the `decode_entry_point` fuzz target
+//! and `tests/decode_corpus.rs` so that both cover the same functions. A new `Decoder::decode_*`
+//! function should be added here
+
+use godot_binary_serialization::{
+    decoder::{DecodeLimits, DecodeOptions, Decoder},
+    types::{ContainerTypeKind, ProtocolVersion, SerializeFlag},
+};
+
+/// The amount of entry points, selectors are taken modulo this
+pub const ENTRY_POINTS: u8 = 53;
+
+/// Chooses the options from the bits of the parameter byte
+pub fn options(param: u8) -> DecodeOptions {
+    DecodeOptions {
+        allow_objects: param & 1 != 0,
+        protocol: if param & 2 != 0 {
+            ProtocolVersion::Godot3
+        } else {
+            ProtocolVersion::Godot4
+        },
+        limits: if param & 4 != 0 {
+            DecodeLimits {
+                max_depth: 4,
+                max_container_length: 16,
+                max_string_bytes: 64,
+                max_total_bytes: 1024,
+            }
+        } else {
+            DecodeLimits::default()
+        },
+    }
+}
+
+/// Chooses a flag from the parameter byte, including flags that don't belong to the type being
+/// decoded
+pub fn flag(param: u8) -> SerializeFlag {
+    let kind = ContainerTypeKind::from_bits(param as u16 >> 3);
+
+    match param % 5 {
+        0 => SerializeFlag::None,
+        1 => SerializeFlag::Bit64,
+        2 => SerializeFlag::TypedArray(kind),
+        3 => SerializeFlag::TypedDictionary {
+            key: kind,
+            value: ContainerTypeKind::from_bits(param as u16 >> 5),
+        },
+        _ => SerializeFlag::ObjectAsId,
+    }
+}
+
+/// Chooses an offset from the parameter byte, offsets near [usize::MAX] check that the decoder
+/// doesn't overflow when adding to them
+pub fn offset(param: u8) -> usize {
+    if param & 0x80 != 0 {
+        usize::MAX - (param & 0x7F) as usize
+    } else {
+        param as usize
+    }
+}
+
+/// Calls the entry point chosen by the selector with the bytes. The parameter chooses the flag,
+/// offset or options for entry points that take them. Errors are fine but panics are not
+pub fn decode_entry_point(selector: u8, param: u8, bytes: &[u8]) {
+    let flag = &flag(param);
+    let offset = offset(param);
+    let options = &options(param);
+    let kind = &ContainerTypeKind::from_bits(param as u16);
+
+    let _decoded = match selector % ENTRY_POINTS {
+        0 => Decoder::get_type_and_flags(bytes).is_ok(),
+        1 => Decoder::get_type_and_flags_with_protocol(bytes, options.protocol).is_ok(),
+        2 => Decoder::decode_variant(bytes).is_ok(),
+        3 => Decoder::decode_variant_with_options(bytes, options).is_ok(),
+        4 => {
+            if let Ok((_, consumed)) = Decoder::decode_variant_consumed(bytes) {
+                assert!(consumed <= bytes.len());
+            }
+            true
+        }
+        5 => {
+            if let Ok((_, consumed)) = Decoder::decode_variant_consumed_with_options(bytes, options)
+            {
+                assert!(consumed <= bytes.len());
+            }
+            true
+        }
+        6 => Decoder::decode_bool(bytes, flag).is_ok(),
+        7 => Decoder::decode_int(bytes, flag).is_ok(),
+        8 => Decoder::decode_raw_int(bytes, offset, flag).is_ok(),
+        9 => Decoder::decode_float(bytes, flag).is_ok(),
+        10 => Decoder::decode_raw_float(bytes, offset, flag).is_ok(),
+        11 => Decoder::decode_string(bytes).is_ok(),
+        12 => Decoder::decode_string_name(bytes).is_ok(),
+        13 => {
+            if let Ok((_, length)) = Decoder::decode_raw_string(bytes, offset) {
+                assert!(offset + length <= bytes.len());
+            }
+            true
+        }
+        14 => Decoder::decode_node_path(bytes).is_ok(),
+        15 => Decoder::decode_vector2(bytes, flag).is_ok(),
+        16 => Decoder::decode_vector3(bytes, flag).is_ok(),
+        17 => Decoder::decode_vector4(bytes, flag).is_ok(),
+        18 => Decoder::decode_vector2i(bytes).is_ok(),
+        19 => Decoder::decode_vector3i(bytes).is_ok(),
+        20 => Decoder::decode_vector4i(bytes).is_ok(),
+        21 => Decoder::decode_rect2(bytes, flag).is_ok(),
+        22 => Decoder::decode_rect2i(bytes).is_ok(),
+        23 => Decoder::decode_aabb(bytes, flag).is_ok(),
+        24 => Decoder::decode_plane(bytes, flag).is_ok(),
+        25 => Decoder::decode_quaternion(bytes, flag).is_ok(),
+        26 => Decoder::decode_transform2d(bytes, flag).is_ok(),
+        27 => Decoder::decode_basis(bytes, flag).is_ok(),
+        28 => Decoder::decode_transform3d(bytes, flag).is_ok(),
+        29 => Decoder::decode_projection(bytes, flag).is_ok(),
+        30 => Decoder::decode_color(bytes).is_ok(),
+        31 => Decoder::decode_rid(bytes).is_ok(),
+        32 => Decoder::decode_callable(bytes).is_ok(),
+        33 => Decoder::decode_signal(bytes).is_ok(),
+        34 => Decoder::decode_object(bytes, flag, options).is_ok(),
+        35 => Decoder::decode_array(bytes).is_ok(),
+        36 => Decoder::decode_array_with_options(bytes, options).is_ok(),
+        37 => Decoder::decode_typed_array(bytes, flag).is_ok(),
+        38 => Decoder::decode_typed_array_with_options(bytes, flag, options).is_ok(),
+        39 => {
+            // An untyped container has no element type to read, so it takes up no bytes anywhere
+            if let Ok((_, length)) = Decoder::decode_container_type(bytes, offset, kind) {
+                assert!(length == 0 || offset + length <= bytes.len());
+            }
+            true
+        }
+        40 => Decoder::decode_dictionary(bytes).is_ok(),
+        41 => Decoder::decode_dictionary_with_options(bytes, options).is_ok(),
+        42 => Decoder::decode_packed_byte_array(bytes).is_ok(),
+        43 => Decoder::decode_packed_int32_array(bytes).is_ok(),
+        44 => Decoder::decode_packed_int64_array(bytes).is_ok(),
+        45 => Decoder::decode_packed_float32_array(bytes).is_ok(),
+        46 => Decoder::decode_packed_float64_array(bytes).is_ok(),
+        47 => Decoder::decode_packed_string_array(bytes).is_ok(),
+        48 => Decoder::decode_packed_vector2_array(bytes, flag).is_ok(),
+        49 => Decoder::decode_packed_vector3_array(bytes, flag).is_ok(),
+        50 => Decoder::decode_packed_vector4_array(bytes, flag).is_ok(),
+        51 => Decoder::decode_packed_color_array(bytes).is_ok(),
+        _ => {
+            let mut decoder = Decoder::from_reader_with_options(bytes, options.clone());
+            while let Ok(Some(_)) = decoder.decode_variant() {}
+            true
+        }
+    };
+}
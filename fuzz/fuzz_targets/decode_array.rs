@@ -0,0 +1,14 @@
+#![no_main]
+
+use godot_binary_serialization::{
+    decoder::Decoder, encoder::Encoder, types::variant::GodotVariant,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(array) = Decoder::decode_array(data) {
+        if let Ok(length) = Encoder::encoded_len(&array) {
+            assert_eq!(array.byte_length(), length);
+        }
+    }
+});
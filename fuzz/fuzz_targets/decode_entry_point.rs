@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../entry_points.rs"]
+mod entry_points;
+
+// The first byte chooses the decode function, the second byte chooses the flag, offset or options
+// it is called with and the rest are decoded
+fuzz_target!(|data: &[u8]| {
+    let [selector, param, bytes @ ..] = data else {
+        return;
+    };
+
+    entry_points::decode_entry_point(*selector, *param, bytes);
+});
@@ -0,0 +1,27 @@
+#![no_main]
+
+use godot_binary_serialization::{
+    decoder::{DecodeOptions, Decoder},
+    encoder::Encoder,
+    types::{variant::GodotVariant, SerializeFlag},
+};
+use libfuzzer_sys::fuzz_target;
+
+// The first byte chooses whether the object is sent as an id and the rest are decoded
+fuzz_target!(|data: &[u8]| {
+    let Some((&choice, bytes)) = data.split_first() else {
+        return;
+    };
+
+    let flag = if choice & 1 != 0 {
+        SerializeFlag::ObjectAsId
+    } else {
+        SerializeFlag::None
+    };
+
+    if let Ok(object) = Decoder::decode_object(bytes, &flag, &DecodeOptions::with_objects()) {
+        if let Ok(length) = Encoder::encoded_len(&object) {
+            assert_eq!(object.byte_length(), length);
+        }
+    }
+});
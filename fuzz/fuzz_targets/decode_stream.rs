@@ -0,0 +1,55 @@
+#![no_main]
+
+use std::io::Read;
+
+use godot_binary_serialization::decoder::Decoder;
+use libfuzzer_sys::fuzz_target;
+
+/// Gives out the bytes a few at a time like a network connection
+struct ChunkReader<'a> {
+    bytes: &'a [u8],
+    chunk_size: usize,
+}
+
+impl Read for ChunkReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = self.chunk_size.min(buf.len()).min(self.bytes.len());
+        let (chunk, rest) = self.bytes.split_at(length);
+
+        buf[..length].copy_from_slice(chunk);
+        self.bytes = rest;
+        Ok(length)
+    }
+}
+
+// The first byte chooses how many bytes each read gives out, the stream should decode the same
+// variants as decoding the slice one variant at a time
+fuzz_target!(|data: &[u8]| {
+    let Some((&chunk_size, bytes)) = data.split_first() else {
+        return;
+    };
+
+    let reader = ChunkReader {
+        bytes,
+        chunk_size: chunk_size as usize + 1,
+    };
+
+    let mut decoder = Decoder::from_reader(reader);
+    let mut offset = 0;
+    loop {
+        let Ok(variant) = decoder.decode_variant() else {
+            assert!(Decoder::decode_variant(&bytes[offset..]).is_err());
+            return;
+        };
+        let Some(variant) = variant else {
+            break;
+        };
+
+        let (expected, consumed) = Decoder::decode_variant_consumed(&bytes[offset..]).unwrap();
+        assert_eq!(variant.byte_length(), expected.byte_length());
+        offset += consumed;
+        assert_eq!(decoder.position(), offset);
+    }
+
+    assert_eq!(offset, bytes.len());
+});
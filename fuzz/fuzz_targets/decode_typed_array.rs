@@ -0,0 +1,22 @@
+#![no_main]
+
+use godot_binary_serialization::{
+    decoder::Decoder,
+    encoder::Encoder,
+    types::{variant::GodotVariant, ContainerTypeKind, SerializeFlag},
+};
+use libfuzzer_sys::fuzz_target;
+
+// The first byte chooses how the element type is written and the rest are decoded
+fuzz_target!(|data: &[u8]| {
+    let Some((&choice, bytes)) = data.split_first() else {
+        return;
+    };
+
+    let flag = SerializeFlag::TypedArray(ContainerTypeKind::from_bits(choice as u16));
+    if let Ok(array) = Decoder::decode_typed_array(bytes, &flag) {
+        if let Ok(length) = Encoder::encoded_len(&array) {
+            assert_eq!(array.byte_length(), length);
+        }
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use godot_binary_serialization::{decoder::Decoder, encoder::Encoder};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((variant, consumed)) = Decoder::decode_variant_consumed(data) {
+        assert!(consumed <= data.len());
+
+        if let Ok(length) = Encoder::encoded_len(&*variant) {
+            assert_eq!(variant.byte_length(), length);
+        }
+    }
+});
@@ -0,0 +1,37 @@
+#![no_main]
+
+use godot_binary_serialization::{
+    decoder::{DecodeLimits, DecodeOptions, Decoder},
+    types::ProtocolVersion,
+};
+use libfuzzer_sys::fuzz_target;
+
+// The first byte chooses the options and the rest are decoded
+fuzz_target!(|data: &[u8]| {
+    let Some((&choice, bytes)) = data.split_first() else {
+        return;
+    };
+
+    let options = DecodeOptions {
+        allow_objects: choice & 1 != 0,
+        protocol: if choice & 2 != 0 {
+            ProtocolVersion::Godot3
+        } else {
+            ProtocolVersion::Godot4
+        },
+        limits: if choice & 4 != 0 {
+            DecodeLimits {
+                max_depth: 4,
+                max_container_length: 16,
+                max_string_bytes: 64,
+                max_total_bytes: 1024,
+            }
+        } else {
+            DecodeLimits::default()
+        },
+    };
+
+    if let Ok((_, consumed)) = Decoder::decode_variant_consumed_with_options(bytes, &options) {
+        assert!(consumed <= bytes.len());
+    }
+});
@@ -0,0 +1,347 @@
+use std::mem;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::{
+        structures::{GodotArray, GodotTypedArray},
+        variant::GodotVariant,
+        ContainerType, ContainerTypeKind, GodotTypeIndex, SerializeFlag,
+    },
+};
+
+use super::{DecodeBudget, DecodeOptions, Decoder};
+
+impl Decoder {
+    /// Decodes bytes into a godot array. Each value in the array is decoded as its own variant.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Typically you would get this from a network request
+    /// let bytes = /* Pretend we have valid bytes here */
+    ///
+    /// let Ok(array) = Decoder::decode_array(&bytes) else {
+    ///     panic!("Invalid bytes");
+    /// }
+    ///
+    /// // Assuming the first value of the array is an integer
+    /// let value = array.get::<GodotInteger>(0);
+    /// ```
+    pub fn decode_array(bytes: &[u8]) -> Result<GodotArray, DecodeError> {
+        Self::decode_array_with_options(bytes, &DecodeOptions::default())
+    }
+
+    /// Decodes bytes into a godot array using the options given for each value
+    pub fn decode_array_with_options(
+        bytes: &[u8],
+        options: &DecodeOptions,
+    ) -> Result<GodotArray, DecodeError> {
+        Self::decode_array_with_budget(bytes, options, &mut DecodeBudget::new(options.limits))
+            .map(|(array, _)| array)
+    }
+
+    /// Decodes bytes into a godot array, counting its values against the budget. Returns the array
+    /// and the amount of bytes it took up
+    pub(crate) fn decode_array_with_budget(
+        bytes: &[u8],
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<(GodotArray, usize), DecodeError> {
+        let (values, byte_pos) = Self::decode_array_values(bytes, 4, None, options, budget)?;
+
+        Ok((GodotArray::new_from_vec(values), byte_pos))
+    }
+
+    /// Decodes bytes into a godot typed array. The flag describes how the element type is written
+    /// after the header, every decoded value must match the element type.
+    pub fn decode_typed_array(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotTypedArray, DecodeError> {
+        Self::decode_typed_array_with_options(bytes, flag, &DecodeOptions::default())
+    }
+
+    /// Decodes bytes into a godot typed array using the options given for each value
+    pub fn decode_typed_array_with_options(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+        options: &DecodeOptions,
+    ) -> Result<GodotTypedArray, DecodeError> {
+        let mut budget = DecodeBudget::new(options.limits);
+        Self::decode_typed_array_with_budget(bytes, flag, options, &mut budget)
+            .map(|(array, _)| array)
+    }
+
+    /// Decodes bytes into a godot typed array, counting its values against the budget. Returns the
+    /// array and the amount of bytes it took up
+    pub(crate) fn decode_typed_array_with_budget(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<(GodotTypedArray, usize), DecodeError> {
+        let SerializeFlag::TypedArray(kind) = flag else {
+            return Err(DecodeError::at_type(
+                DecodeErrorKind::InvalidFlag(*flag),
+                GodotTypeIndex::Array,
+            ));
+        };
+
+        let (element_type, type_length) =
+            Self::decode_container_type_with_budget(bytes, 4, kind, budget)?;
+        let offset = 4 + type_length;
+        let (values, byte_pos) =
+            Self::decode_array_values(bytes, offset, Some(&element_type), options, budget)?;
+
+        let mut array = GodotTypedArray::new(element_type);
+        array.values = values;
+
+        Ok((array, byte_pos))
+    }
+
+    /// Decodes the element type of a typed container starting at the offset in bytes. Returns the
+    /// container type and the amount of bytes it took up
+    pub fn decode_container_type(
+        bytes: &[u8],
+        offset: usize,
+        kind: &ContainerTypeKind,
+    ) -> Result<(ContainerType, usize), DecodeError> {
+        Self::decode_container_type_with_budget(bytes, offset, kind, &mut DecodeBudget::unlimited())
+    }
+
+    /// Decodes the element type of a typed container, counting a class name or script path
+    /// against the budget
+    pub(crate) fn decode_container_type_with_budget(
+        bytes: &[u8],
+        offset: usize,
+        kind: &ContainerTypeKind,
+        budget: &mut DecodeBudget,
+    ) -> Result<(ContainerType, usize), DecodeError> {
+        match kind {
+            ContainerTypeKind::None => Ok((ContainerType::builtin(GodotTypeIndex::Nil), 0)),
+            ContainerTypeKind::Builtin => {
+                let type_idx = LittleEndian::read_u32(Self::bytes_at(bytes, offset, 4)?);
+                let Some(builtin_type) = u16::try_from(type_idx)
+                    .ok()
+                    .and_then(|idx| GodotTypeIndex::try_from(idx).ok())
+                else {
+                    return Err(DecodeError::new(
+                        DecodeErrorKind::UnknownType(type_idx),
+                        offset,
+                    ));
+                };
+
+                Ok((ContainerType::builtin(builtin_type), 4))
+            }
+            ContainerTypeKind::ClassName => {
+                let (class_name, length) =
+                    Self::decode_raw_string_with_budget(bytes, offset, budget)?;
+
+                Ok((ContainerType::class_name(&class_name), length))
+            }
+            ContainerTypeKind::Script => {
+                let (path, length) = Self::decode_raw_string_with_budget(bytes, offset, budget)?;
+
+                Ok((ContainerType::script(&path), length))
+            }
+        }
+    }
+
+    /// Decodes the length of an array at the offset in bytes and then each of its values, checking
+    /// each value against the element type of a typed array. Returns the values and the position
+    /// in bytes after the last value
+    fn decode_array_values(
+        bytes: &[u8],
+        offset: usize,
+        element_type: Option<&ContainerType>,
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<(Vec<Box<dyn GodotVariant>>, usize), DecodeError> {
+        // The last bit was used to mark an array as shared in older versions of godot
+        let array_length =
+            (LittleEndian::read_u32(Self::bytes_at(bytes, offset, 4)?) & 0x7FFFFFFF) as usize;
+        budget.check_container_length(array_length, offset)?;
+        budget.allocate(
+            array_length.saturating_mul(mem::size_of::<Box<dyn GodotVariant>>()),
+            offset,
+        )?;
+
+        let mut values = Vec::new();
+        let mut byte_pos = offset + 4;
+        for _ in 0..array_length {
+            let (value, length) = Self::decode_nested_variant(bytes, byte_pos, options, budget)?;
+            if let Some(element_type) = element_type {
+                element_type.check(&*value).map_err(|error| {
+                    DecodeError::at_type(error.into(), GodotTypeIndex::Array).offset_by(byte_pos)
+                })?;
+            }
+            byte_pos += length;
+
+            values.push(value);
+        }
+
+        Ok((values, byte_pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decoder::{DecodeLimits, DecodeOptions, Decoder},
+        error::DecodeErrorKind,
+        types::{
+            primitive::{GodotInteger, GodotString},
+            structures::{GodotArray, GodotTypedArray},
+            variant::{AsVariant, GodotVariant},
+            ContainerType, ContainerTypeKind, GodotTypeIndex,
+        },
+    };
+
+    #[test]
+    fn decode_array() {
+        let bytes = [
+            28, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 28, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0,
+            2, 0, 0, 0, 104, 105, 0, 0,
+        ];
+
+        let array = Decoder::decode_array(&bytes).unwrap();
+        let int = array.get::<GodotInteger>(0).unwrap();
+        let inner = array.get::<GodotArray>(1).unwrap();
+        let string = inner.get::<GodotString>(0).unwrap();
+
+        assert_eq!(int.value, 1);
+        assert_eq!(string.value, "hi");
+        assert_eq!(
+            array.byte_length(),
+            bytes.len(),
+            "Expected byte length of {} but got {} instead",
+            bytes.len(),
+            array.byte_length()
+        );
+    }
+
+    #[test]
+    fn decode_array_truncated() {
+        // A string of length 5 with only 2 bytes left
+        let bytes = [28, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 104, 105];
+
+        let error = Decoder::decode_array(&bytes).unwrap_err();
+
+        // The string needs its length, 5 bytes and 3 bytes of padding
+        assert_eq!(error.kind, DecodeErrorKind::NotEnoughBytes { needed: 24 });
+        assert_eq!(error.type_idx, Some(GodotTypeIndex::String));
+        assert_eq!(error.offset, 12);
+
+        // The reason is the source of the error so a reporter prints it once
+        let message = error.to_string();
+        let source = std::error::Error::source(&error).map(ToString::to_string);
+        assert_eq!(message, "failed to decode String at byte 12");
+        assert_eq!(
+            source.as_deref(),
+            Some("not enough bytes, at least 24 are needed")
+        );
+    }
+
+    #[test]
+    fn decode_array_too_deep() {
+        let bytes = [28, 0, 0, 0, 1, 0, 0, 0].repeat(200);
+
+        let error = Decoder::decode_array(&bytes).unwrap_err();
+
+        assert_eq!(error.kind, DecodeErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn decode_array_at_max_depth() {
+        let options = DecodeOptions::with_limits(DecodeLimits {
+            max_depth: 4,
+            ..Default::default()
+        });
+
+        // Three arrays holding one array each and an empty array at the bottom are four levels
+        let mut bytes = [28, 0, 0, 0, 1, 0, 0, 0].repeat(3);
+        bytes.extend([28, 0, 0, 0, 0, 0, 0, 0]);
+        let result = Decoder::decode_array_with_options(&bytes, &options);
+        assert!(result.is_ok(), "Expected an array but got {:?}", result);
+
+        // One more level is too deep
+        let mut bytes = [28, 0, 0, 0, 1, 0, 0, 0].repeat(4);
+        bytes.extend([28, 0, 0, 0, 0, 0, 0, 0]);
+        let error = Decoder::decode_array_with_options(&bytes, &options).unwrap_err();
+        assert_eq!(error.kind, DecodeErrorKind::TooDeep);
+        assert_eq!(error.offset, 32);
+    }
+
+    #[test]
+    fn decode_array_too_long() {
+        // Claims to hold a billion values
+        let bytes = [28, 0, 0, 0, 0, 202, 154, 59, 0, 0, 0, 0];
+        let options = DecodeOptions::with_limits(DecodeLimits {
+            max_container_length: 1024,
+            ..Default::default()
+        });
+
+        let error = Decoder::decode_array_with_options(&bytes, &options).unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            DecodeErrorKind::ContainerTooLong {
+                length: 1_000_000_000,
+                max: 1024
+            }
+        );
+        assert_eq!(error.offset, 4);
+    }
+
+    #[test]
+    fn decode_typed_array() {
+        let bytes = [
+            28, 0, 1, 0, 2, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 5, 0, 0, 0, 2, 0, 0, 0, 6, 0, 0, 0,
+        ];
+
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let array = variant.as_var::<GodotTypedArray>().unwrap();
+
+        assert_eq!(
+            array.element_type,
+            ContainerType::builtin(GodotTypeIndex::Integer)
+        );
+        assert_eq!(array.get::<GodotInteger>(1).unwrap().value, 6);
+        assert_eq!(array.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_typed_array_class_name() {
+        let bytes = [28, 0, 2, 0, 4, 0, 0, 0, 78, 111, 100, 101, 0, 0, 0, 0];
+
+        let (_type, flag) = Decoder::get_type_and_flags(&bytes).unwrap();
+        let array = Decoder::decode_typed_array(&bytes, &flag).unwrap();
+
+        assert_eq!(array.element_type, ContainerType::class_name("Node"));
+        assert!(array.is_empty());
+        assert_eq!(array.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_container_type_at_huge_offset() {
+        // Offsets are given by the caller, one next to the end of memory must not overflow
+        let bytes = [28, 0, 1, 0, 2, 0, 0, 0];
+
+        for kind in [
+            ContainerTypeKind::Builtin,
+            ContainerTypeKind::ClassName,
+            ContainerTypeKind::Script,
+        ] {
+            let error = Decoder::decode_container_type(&bytes, usize::MAX - 1, &kind).unwrap_err();
+            let expected = DecodeErrorKind::NotEnoughBytes { needed: usize::MAX };
+
+            assert_eq!(
+                expected, error.kind,
+                "Expected {:?} but got {:?}",
+                expected, error.kind
+            );
+        }
+    }
+}
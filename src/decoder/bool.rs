@@ -1,17 +1,22 @@
-use anyhow::anyhow;
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::{primitive::GodotBool, SerializeFlag, TYPE_PADDING};
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::{primitive::GodotBool, GodotTypeIndex, SerializeFlag, TYPE_PADDING},
+};
 
 use super::Decoder;
 
 impl Decoder {
     /// Decodes bytes into a Godot bool
-    pub fn decode_bool(bytes: &[u8], flag: &SerializeFlag) -> anyhow::Result<GodotBool> {
+    pub fn decode_bool(bytes: &[u8], _flag: &SerializeFlag) -> Result<GodotBool, DecodeError> {
         let length = GodotBool::BIT_SIZE;
         if bytes.len() < TYPE_PADDING as usize + length {
-            return Err(anyhow!(
-                "Byte slice too short to decode int with flag {flag:?}"
+            return Err(DecodeError::at_type(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: TYPE_PADDING as usize + length,
+                },
+                GodotTypeIndex::Bool,
             ));
         }
 
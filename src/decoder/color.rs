@@ -0,0 +1,52 @@
+use crate::{
+    error::DecodeError,
+    types::{structures::GodotColor, GodotTypeIndex, SerializeFlag},
+};
+
+use super::Decoder;
+
+impl Decoder {
+    /// Decodes bytes into a Color. This will fail if the inner bytes can't be decoded into a float
+    pub fn decode_color(bytes: &[u8]) -> Result<GodotColor, DecodeError> {
+        Self::check_length(bytes, 20, GodotTypeIndex::Color)?;
+
+        let r = Decoder::decode_raw_float(bytes, 4, &SerializeFlag::None)?.value as f32;
+        let g = Decoder::decode_raw_float(bytes, 8, &SerializeFlag::None)?.value as f32;
+        let b = Decoder::decode_raw_float(bytes, 12, &SerializeFlag::None)?.value as f32;
+        let a = Decoder::decode_raw_float(bytes, 16, &SerializeFlag::None)?.value as f32;
+
+        Ok(GodotColor { r, g, b, a })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decoder::Decoder, types::structures::GodotColor};
+
+    #[test]
+    fn decode_color() {
+        let bytes = [
+            20, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 63, 0, 0, 0, 0, 0, 0, 128, 63,
+        ];
+        let color = Decoder::decode_color(&bytes).unwrap();
+
+        assert_eq!(color, GodotColor::new(1.0, 0.5, 0.0, 1.0));
+        assert_eq!(color.to_rgba8(), [255, 128, 0, 255]);
+        assert_eq!(color.to_html(false), "ff8000");
+    }
+
+    #[test]
+    fn color_from_html() {
+        let color = GodotColor::from_html("#ff800080").unwrap();
+
+        assert_eq!(color.to_rgba8(), [255, 128, 0, 128]);
+        assert_eq!(
+            GodotColor::from_html("f80").unwrap().to_html(true),
+            "ff8800ff"
+        );
+        assert!(GodotColor::from_html("#ff80").is_ok());
+        assert!(GodotColor::from_html("#ff8").is_ok());
+        assert!(GodotColor::from_html("#ff80000").is_err());
+        assert!(GodotColor::from_html("#gg8000").is_err());
+    }
+}
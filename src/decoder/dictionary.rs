@@ -1,9 +1,21 @@
+use std::mem;
+
 use byteorder::{ByteOrder, LittleEndian};
-use indexmap::IndexMap;
 
-use crate::types::{primitive::GodotNull, structures::GodotDictionary};
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::{
+        structures::GodotDictionary, variant::GodotVariant, ContainerTypeKind, GodotTypeIndex,
+        SerializeFlag,
+    },
+};
+
+use super::{DecodeBudget, DecodeOptions, Decoder};
 
-use super::Decoder;
+/// The size in memory of an entry in a dictionary, the map keeps the hash next to the key and
+/// value and an index to find them
+const ENTRY_SIZE: usize =
+    mem::size_of::<(u64, Box<dyn GodotVariant>, Box<dyn GodotVariant>)>() + mem::size_of::<usize>();
 
 impl Decoder {
     /// Decodes bytes into a godot dictionary.
@@ -25,40 +37,92 @@ impl Decoder {
     /// let value = dictionary.map.get(&key);
     ///
     /// ```
-    pub fn decode_dictionary(bytes: &[u8]) -> anyhow::Result<GodotDictionary> {
-        let mut dict = GodotDictionary {
-            map: IndexMap::new(),
-            byte_size: 0,
-        };
+    ///
+    /// Typed dictionaries have their key and value types decoded from the header flags, every entry
+    /// must match those types.
+    pub fn decode_dictionary(bytes: &[u8]) -> Result<GodotDictionary, DecodeError> {
+        Self::decode_dictionary_with_options(bytes, &DecodeOptions::default())
+    }
 
-        let dict_length = LittleEndian::read_u32(&bytes[4..8]);
+    /// Decodes bytes into a godot dictionary using the options given for each key and value
+    pub fn decode_dictionary_with_options(
+        bytes: &[u8],
+        options: &DecodeOptions,
+    ) -> Result<GodotDictionary, DecodeError> {
+        Self::decode_dictionary_with_budget(bytes, options, &mut DecodeBudget::new(options.limits))
+            .map(|(dict, _)| dict)
+    }
 
-        let mut byte_pos = 8;
-        for _ in 0..dict_length {
-            let key = Self::decode_variant(&bytes[byte_pos..])?;
-            byte_pos += key.byte_length();
+    /// Decodes bytes into a godot dictionary, counting its entries against the budget. Returns the
+    /// dictionary and the amount of bytes it took up, which is more than its encoded length when a
+    /// key was sent more than once
+    pub(crate) fn decode_dictionary_with_budget(
+        bytes: &[u8],
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<(GodotDictionary, usize), DecodeError> {
+        let mut dict = GodotDictionary::new();
+
+        let mut byte_pos = 4;
+        let (_, flag) = Self::get_type_and_flags_with_protocol(bytes, options.protocol)?;
+
+        if let SerializeFlag::TypedDictionary { key, value } = flag {
+            let (key_type, key_length) =
+                Self::decode_container_type_with_budget(bytes, byte_pos, &key, budget)?;
+            byte_pos += key_length;
+            let (value_type, value_length) =
+                Self::decode_container_type_with_budget(bytes, byte_pos, &value, budget)?;
+            byte_pos += value_length;
+
+            dict.key_type = (key != ContainerTypeKind::None).then_some(key_type);
+            dict.value_type = (value != ContainerTypeKind::None).then_some(value_type);
+        }
+
+        if bytes.len() < byte_pos + 4 {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: byte_pos + 4,
+                },
+                byte_pos,
+            ));
+        }
 
-            if key.as_any().is::<GodotNull>() {
-                continue;
-            }
+        let dict_length =
+            (LittleEndian::read_u32(&bytes[byte_pos..byte_pos + 4]) & 0x7FFFFFFF) as usize;
+        budget.check_container_length(dict_length, byte_pos)?;
+        budget.allocate(dict_length.saturating_mul(ENTRY_SIZE), byte_pos)?;
+
+        byte_pos += 4;
+        for _ in 0..dict_length {
+            let key_pos = byte_pos;
+            let (key, key_length) = Self::decode_nested_variant(bytes, key_pos, options, budget)?;
+            byte_pos += key_length;
 
-            let value = Self::decode_variant(&bytes[byte_pos..])?;
-            byte_pos += value.byte_length();
+            let (value, value_length) =
+                Self::decode_nested_variant(bytes, byte_pos, options, budget)?;
+            byte_pos += value_length;
 
+            dict.validate_entry(&*key, &*value).map_err(|error| {
+                DecodeError::at_type(error.into(), GodotTypeIndex::Dictionary).offset_by(key_pos)
+            })?;
             dict.map.insert(key, value);
         }
 
-        dict.byte_size = byte_pos;
-
-        Ok(dict)
+        Ok((dict, byte_pos))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        decoder::Decoder,
-        types::{primitive::GodotString, structures::GodotVector2, variant::GodotVariant},
+        decoder::{DecodeLimits, DecodeOptions, Decoder},
+        error::DecodeErrorKind,
+        types::{
+            primitive::{GodotInteger, GodotNull, GodotString},
+            structures::{GodotDictionary, GodotVector2},
+            variant::GodotVariant,
+            ContainerType, GodotTypeIndex, ProtocolVersion,
+        },
     };
 
     #[test]
@@ -70,11 +134,32 @@ mod tests {
             70, 0, 184, 150, 69,
         ];
 
-        let dict = Decoder::decode_dictionary(&bytes).unwrap();
+        // The dictionary id is from godot 3
+        let options = DecodeOptions::with_protocol(ProtocolVersion::Godot3);
+        let dict = Decoder::decode_dictionary_with_options(&bytes, &options).unwrap();
         let value = dict.get::<GodotVector2>(GodotString::new("position"));
 
         println!("{:?}", value);
         println!("{:?}", dict);
+        assert!(value.is_some());
+    }
+
+    #[test]
+    fn decode_null_key() {
+        // { null: 1, "a": 2 }
+        let bytes = [
+            27, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0,
+            97, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0,
+        ];
+
+        let dict = Decoder::decode_dictionary(&bytes).unwrap();
+        let null = dict.get::<GodotInteger>(GodotNull).map(|value| value.value);
+        let a = dict
+            .get::<GodotInteger>(GodotString::new("a"))
+            .map(|value| value.value);
+
+        assert_eq!(null, Some(1), "Expected Some(1) but got {:?}", null);
+        assert_eq!(a, Some(2), "Expected Some(2) but got {:?}", a);
     }
 
     #[test]
@@ -90,12 +175,71 @@ mod tests {
             98, 97, 110, 97, 110, 97,
         ];
 
-        let dict = Decoder::decode_dictionary(&bytes).unwrap();
+        // The dictionary ids are from godot 3
+        let options = DecodeOptions::with_protocol(ProtocolVersion::Godot3);
+        let dict = Decoder::decode_dictionary_with_options(&bytes, &options).unwrap();
         let key = Box::new(GodotString::new("position")) as Box<dyn GodotVariant>;
 
         let value = dict.map.get(&key);
 
         println!("{:?}", value);
         println!("{:?}", dict);
+
+        let data = dict.get::<GodotDictionary>(GodotString::new("data"));
+        assert!(data.is_some(), "Expected the nested godot 3 dictionary to decode");
+    }
+
+    #[test]
+    fn decode_typed_dictionary() {
+        let bytes = [
+            27, 0, 5, 0, 4, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 104, 112, 0,
+            0, 2, 0, 0, 0, 100, 0, 0, 0,
+        ];
+
+        let dict = Decoder::decode_dictionary(&bytes).unwrap();
+        let value = dict.get::<GodotInteger>(GodotString::new("hp")).unwrap();
+
+        assert_eq!(
+            dict.key_type,
+            Some(ContainerType::builtin(GodotTypeIndex::String))
+        );
+        assert_eq!(
+            dict.value_type,
+            Some(ContainerType::builtin(GodotTypeIndex::Integer))
+        );
+        assert_eq!(value.value, 100);
+        assert_eq!(dict.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_typed_dictionary_mismatch() {
+        // Dictionary[String, int] holding a string value
+        let bytes = [
+            27, 0, 5, 0, 4, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 104, 112, 0,
+            0, 4, 0, 0, 0, 1, 0, 0, 0, 97, 0, 0, 0,
+        ];
+
+        assert!(Decoder::decode_dictionary(&bytes).is_err());
+    }
+
+    #[test]
+    fn decode_dictionary_allocation_too_large() {
+        // Every entry is small but together they go over the limit
+        let bytes = [
+            27, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 2,
+            0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0,
+        ];
+        let options = DecodeOptions::with_limits(DecodeLimits {
+            max_total_bytes: 64,
+            ..Default::default()
+        });
+
+        assert!(Decoder::decode_dictionary(&bytes).is_ok());
+        assert_eq!(
+            Decoder::decode_dictionary_with_options(&bytes, &options)
+                .unwrap_err()
+                .kind,
+            DecodeErrorKind::AllocationTooLarge { max: 64 }
+        );
     }
 }
@@ -1,13 +1,15 @@
-use anyhow::anyhow;
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::{primitive::GodotFloat, SerializeFlag, TYPE_PADDING};
+use crate::{
+    error::DecodeError,
+    types::{primitive::GodotFloat, SerializeFlag},
+};
 
 use super::Decoder;
 
 impl Decoder {
     /// Decodes a bytes into a Godot float
-    pub fn decode_float(bytes: &[u8], flag: &SerializeFlag) -> anyhow::Result<GodotFloat> {
+    pub fn decode_float(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotFloat, DecodeError> {
         Self::decode_raw_float(bytes, 4, flag)
     }
 
@@ -16,29 +18,25 @@ impl Decoder {
         bytes: &[u8],
         offset: usize,
         flag: &SerializeFlag,
-    ) -> anyhow::Result<GodotFloat> {
+    ) -> Result<GodotFloat, DecodeError> {
         let mut length = 4;
 
         if flag == &SerializeFlag::Bit64 {
             length = 8;
         }
 
-        if bytes.len() < TYPE_PADDING as usize + length {
-            return Err(anyhow!(
-                "Byte slice too short to decode float with flag {flag:?}"
-            ));
-        }
+        let bytes = Self::bytes_at(bytes, offset, length)?;
 
         if flag == &SerializeFlag::Bit64 {
             return Ok(GodotFloat {
-                value: LittleEndian::read_f64(&bytes[offset..offset + length]),
-                byte_size: TYPE_PADDING as usize + length,
+                value: LittleEndian::read_f64(bytes),
+                double_precision: true,
             });
         }
 
         Ok(GodotFloat {
-            value: LittleEndian::read_f32(&bytes[offset..offset + length]) as f64,
-            byte_size: TYPE_PADDING as usize + length,
+            value: LittleEndian::read_f32(bytes) as f64,
+            double_precision: false,
         })
     }
 }
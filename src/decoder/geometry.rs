@@ -0,0 +1,145 @@
+use crate::{
+    error::DecodeError,
+    types::{
+        geometry::{GodotAabb, GodotPlane, GodotRect2, GodotRect2i},
+        structures::{GodotVector2, GodotVector2i, GodotVector3},
+        GodotTypeIndex, SerializeFlag,
+    },
+};
+
+use super::Decoder;
+
+impl Decoder {
+    /// Decodes bytes into a Rect2. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_rect2(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotRect2, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 4, flag)?;
+
+        Ok(GodotRect2 {
+            position: GodotVector2::new(c[0], c[1]),
+            size: GodotVector2::new(c[2], c[3]),
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Rect2i. This will fail if the inner bytes can't be decoded into an
+    /// integer
+    pub fn decode_rect2i(bytes: &[u8]) -> Result<GodotRect2i, DecodeError> {
+        Self::check_length(bytes, 20, GodotTypeIndex::Rect2I)?;
+
+        let x = Decoder::decode_raw_int(bytes, 4, &SerializeFlag::None)?.value as i32;
+        let y = Decoder::decode_raw_int(bytes, 8, &SerializeFlag::None)?.value as i32;
+        let width = Decoder::decode_raw_int(bytes, 12, &SerializeFlag::None)?.value as i32;
+        let height = Decoder::decode_raw_int(bytes, 16, &SerializeFlag::None)?.value as i32;
+
+        Ok(GodotRect2i {
+            position: GodotVector2i::new(x, y),
+            size: GodotVector2i::new(width, height),
+        })
+    }
+
+    /// Decodes bytes into an AABB. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_aabb(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotAabb, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 6, flag)?;
+
+        Ok(GodotAabb {
+            position: GodotVector3::new(c[0], c[1], c[2]),
+            size: GodotVector3::new(c[3], c[4], c[5]),
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Plane. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_plane(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotPlane, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 4, flag)?;
+
+        Ok(GodotPlane {
+            normal: GodotVector3::new(c[0], c[1], c[2]),
+            d: c[3],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        types::{
+            geometry::{GodotAabb, GodotPlane, GodotRect2},
+            structures::{GodotVector2, GodotVector3},
+            variant::{AsVariant, GodotVariant},
+            SerializeFlag,
+        },
+    };
+
+    #[test]
+    fn decode_rect2() {
+        let bytes = [
+            7, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 64, 0, 0, 32, 65, 0, 0, 160, 64,
+        ];
+        let rect2 = Decoder::decode_rect2(&bytes, &SerializeFlag::None).unwrap();
+        let value = GodotRect2::new(GodotVector2::new(1.0, 2.0), GodotVector2::new(10.0, 5.0));
+
+        assert_eq!(
+            rect2, value,
+            "Expected value of {:?} but got {:?} instead",
+            value, rect2
+        );
+    }
+
+    #[test]
+    fn decode_rect2_double() {
+        let mut bytes = vec![7, 0, 1, 0];
+        for component in [1.5_f64, -2.25, 0.1, 1e300] {
+            bytes.extend_from_slice(&component.to_le_bytes());
+        }
+
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let rect2 = variant.as_var::<GodotRect2>().unwrap();
+
+        assert_eq!(rect2.size, GodotVector2::new(0.1, 1e300));
+        assert!(rect2.double_precision);
+        assert_eq!(rect2.byte_length(), bytes.len());
+        assert_eq!(Encoder::encode_rect2(rect2).unwrap(), bytes);
+    }
+
+    #[test]
+    fn decode_aabb() {
+        let bytes = [
+            16, 0, 0, 0, 0, 0, 128, 191, 0, 0, 128, 191, 0, 0, 128, 191, 0, 0, 0, 64, 0, 0, 0, 64,
+            0, 0, 0, 64,
+        ];
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let aabb = variant.as_var::<GodotAabb>().unwrap();
+        let value = GodotAabb::new(
+            GodotVector3::new(-1.0, -1.0, -1.0),
+            GodotVector3::new(2.0, 2.0, 2.0),
+        );
+
+        assert_eq!(
+            *aabb, value,
+            "Expected value of {:?} but got {:?} instead",
+            value, aabb
+        );
+    }
+
+    #[test]
+    fn decode_plane() {
+        let bytes = [
+            14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 0, 0, 0, 160, 64,
+        ];
+        let plane = Decoder::decode_plane(&bytes, &SerializeFlag::None).unwrap();
+        let value = GodotPlane::new(GodotVector3::new(0.0, 1.0, 0.0), 5.0);
+
+        assert_eq!(
+            plane, value,
+            "Expected value of {:?} but got {:?} instead",
+            value, plane
+        );
+        assert!(!plane.is_point_over(GodotVector3::new(0.0, 4.0, 0.0)));
+    }
+}
@@ -1,13 +1,15 @@
-use anyhow::anyhow;
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::{primitive::GodotInteger, SerializeFlag, TYPE_PADDING};
+use crate::{
+    error::DecodeError,
+    types::{primitive::GodotInteger, SerializeFlag},
+};
 
 use super::Decoder;
 
 impl Decoder {
     /// Decodes bytes into a Godot integer
-    pub fn decode_int(bytes: &[u8], flag: &SerializeFlag) -> anyhow::Result<GodotInteger> {
+    pub fn decode_int(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotInteger, DecodeError> {
         Self::decode_raw_int(bytes, 4, flag)
     }
 
@@ -16,30 +18,26 @@ impl Decoder {
         bytes: &[u8],
         offset: usize,
         flag: &SerializeFlag,
-    ) -> anyhow::Result<GodotInteger> {
-        let mut length = 4;
+    ) -> Result<GodotInteger, DecodeError> {
+        let length = Self::int_size(flag);
+
+        let bytes = Self::bytes_at(bytes, offset, length)?;
 
         if flag == &SerializeFlag::Bit64 {
-            length = 8;
+            return Ok(GodotInteger::new_from_i64(LittleEndian::read_i64(bytes)));
         }
 
-        if bytes.len() < TYPE_PADDING as usize + length {
-            return Err(anyhow!(
-                "Byte slice too short to decode int with flag {flag:?}"
-            ));
-        }
+        Ok(GodotInteger::new_from_i32(LittleEndian::read_i32(bytes)))
+    }
 
+    /// The amount of bytes an integer takes up after its header. Godot decides this from the value
+    /// but the flag is what was sent, so a small integer can still take up 64 bits
+    pub(crate) fn int_size(flag: &SerializeFlag) -> usize {
         if flag == &SerializeFlag::Bit64 {
-            return Ok(GodotInteger {
-                value: LittleEndian::read_i64(&bytes[offset..offset + length]),
-                byte_size: TYPE_PADDING as usize + length,
-            });
+            8
+        } else {
+            4
         }
-
-        Ok(GodotInteger {
-            value: LittleEndian::read_i32(&bytes[offset..offset + length]) as i64,
-            byte_size: TYPE_PADDING as usize + length,
-        })
     }
 }
 
@@ -73,4 +71,14 @@ mod tests {
             value, int.value
         );
     }
+
+    #[test]
+    fn decode_small_int64() {
+        // Godot would send 7 as 32 bits, so it takes up more bytes than it encodes back into
+        let bytes: &[u8] = &[2, 0, 1, 0, 7, 0, 0, 0, 0, 0, 0, 0];
+        let (int, consumed) = Decoder::decode_variant_consumed(bytes).unwrap();
+
+        assert_eq!(consumed, bytes.len());
+        assert_eq!(int.byte_length(), 8);
+    }
 }
@@ -1,51 +1,524 @@
+pub mod array;
+pub mod color;
 pub mod dictionary;
 pub mod float;
+pub mod geometry;
 pub mod int;
+pub mod node_path;
+pub mod object;
+pub mod packed;
+pub mod stream;
 pub mod string;
+pub mod transform;
 pub mod vector;
 pub mod bool;
 
-use anyhow::anyhow;
+use std::mem;
+
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::{primitive::GodotNull, variant::GodotVariant, SerializeFlag, GodotTypeIndex};
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::{
+        object::GodotRid, primitive::GodotNull, variant::GodotVariant, GodotTypeIndex,
+        ProtocolVersion, SerializeFlag, TYPE_PADDING,
+    },
+};
 
 pub struct Decoder;
 
+/// A decoded variant and the amount of bytes it took up
+type DecodedVariant = (Box<dyn GodotVariant + 'static>, usize);
+
+/// Changes how the decoder treats the bytes it is given. The default options match godot's
+/// `bytes_to_var`
+#[derive(Debug, Clone, Default)]
+pub struct DecodeOptions {
+    /// Whether objects sent with their class name and properties can be decoded, this matches
+    /// godot's `bytes_to_var_with_objects`. The sender chooses the class and every property of
+    /// these objects so this should only be enabled for trusted sources. Objects sent as an
+    /// instance id can always be decoded
+    pub allow_objects: bool,
+    /// The version of godot that encoded the bytes
+    pub protocol: ProtocolVersion,
+    /// How much a packet can make the decoder nest and allocate
+    pub limits: DecodeLimits,
+}
+
+impl DecodeOptions {
+    /// The default options but with full objects allowed
+    pub fn with_objects() -> Self {
+        Self {
+            allow_objects: true,
+            ..Default::default()
+        }
+    }
+
+    /// The default options for bytes encoded by a version of godot
+    pub fn with_protocol(protocol: ProtocolVersion) -> Self {
+        Self {
+            protocol,
+            ..Default::default()
+        }
+    }
+
+    /// The default options but with different limits
+    pub fn with_limits(limits: DecodeLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+}
+
+/// Limits on how much work a packet can make the decoder do. Decoding fails with a specific
+/// [DecodeErrorKind] as soon as a limit would be exceeded, before the strings and lists that would
+/// exceed it are allocated.
+/// The defaults are large enough for the packets a game usually sends while keeping what a single
+/// packet can allocate to a few MiB, servers that know what their clients send should lower them
+///
+/// # Example
+///
+/// ```rust
+/// use godot_binary_serialization::decoder::DecodeLimits;
+///
+/// let limits = DecodeLimits {
+///     max_container_length: 1024,
+///     max_string_bytes: 4096,
+///     max_total_bytes: 1024 * 1024,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// How many levels of variants can be nested inside of each other, the variant at the top is
+    /// the first level. Without a limit a small packet of nested arrays could overflow the stack.
+    /// Godot allows 1024 but that needs more stack than a thread usually has in debug builds so
+    /// the default is 128
+    pub max_depth: usize,
+    /// The most values an array, dictionary, packed array, object or node path can hold. The
+    /// default is 2^20
+    pub max_container_length: usize,
+    /// The most bytes a single string can hold. The default is 1 MiB
+    pub max_string_bytes: usize,
+    /// The most bytes the decoded values can take up in memory altogether. This is counted from
+    /// the size of each value and the strings and lists they hold, so it is an estimate of the
+    /// memory used rather than an exact amount. The default is 8 MiB
+    pub max_total_bytes: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_container_length: 1 << 20,
+            max_string_bytes: 1 << 20,
+            max_total_bytes: 8 << 20,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// No limits at all, packets are only limited by the amount of bytes they have. Without a depth
+    /// limit deeply nested containers can overflow the stack so this is only for trusted bytes
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_container_length: usize::MAX,
+            max_string_bytes: usize::MAX,
+            max_total_bytes: usize::MAX,
+        }
+    }
+}
+
+/// Keeps track of how deep the decoder is and how much it has allocated while decoding a packet,
+/// so that the limits apply to the packet as a whole
+#[derive(Debug, Clone)]
+pub(crate) struct DecodeBudget {
+    limits: DecodeLimits,
+    depth: usize,
+    allocated: usize,
+}
+
+impl DecodeBudget {
+    pub(crate) fn new(limits: DecodeLimits) -> Self {
+        Self {
+            limits,
+            depth: 0,
+            allocated: 0,
+        }
+    }
+
+    /// A budget for the decoders that don't take options
+    pub(crate) fn unlimited() -> Self {
+        Self::new(DecodeLimits::unlimited())
+    }
+
+    /// Fails if a container at the offset in bytes holds more values than the limit
+    pub(crate) fn check_container_length(
+        &self,
+        length: usize,
+        offset: usize,
+    ) -> Result<(), DecodeError> {
+        let max = self.limits.max_container_length;
+        if length > max {
+            return Err(DecodeError::new(
+                DecodeErrorKind::ContainerTooLong { length, max },
+                offset,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Fails if a string at the offset in bytes is longer than the limit
+    pub(crate) fn check_string_length(
+        &self,
+        length: usize,
+        offset: usize,
+    ) -> Result<(), DecodeError> {
+        let max = self.limits.max_string_bytes;
+        if length > max {
+            return Err(DecodeError::new(
+                DecodeErrorKind::StringTooLong { length, max },
+                offset,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Counts bytes that are about to be allocated for the value at the offset in bytes, failing
+    /// if it takes the total over the limit
+    pub(crate) fn allocate(&mut self, size: usize, offset: usize) -> Result<(), DecodeError> {
+        let max = self.limits.max_total_bytes;
+        let allocated = self.allocated.saturating_add(size);
+        if allocated > max {
+            return Err(DecodeError::new(
+                DecodeErrorKind::AllocationTooLarge { max },
+                offset,
+            ));
+        }
+
+        self.allocated = allocated;
+        Ok(())
+    }
+}
+
 impl Decoder {
     /// Gets the type and flags of the bytes passed. The type determines which type we should try
     /// and decode it as, and the flag shows how we will decode the type
-    pub fn get_type_and_flags(bytes: &[u8]) -> anyhow::Result<(GodotTypeIndex, SerializeFlag)> {
-        let Ok(type_idx) = GodotTypeIndex::try_from(LittleEndian::read_u16(&bytes[0..2])) else {
-            return Err(anyhow!("Unsupported type index"));
+    pub fn get_type_and_flags(
+        bytes: &[u8],
+    ) -> Result<(GodotTypeIndex, SerializeFlag), DecodeError> {
+        Self::get_type_and_flags_with_protocol(bytes, ProtocolVersion::Godot4)
+    }
+
+    /// Gets the type and flags of the bytes passed using the type ids and header rules of a
+    /// protocol version
+    pub fn get_type_and_flags_with_protocol(
+        bytes: &[u8],
+        protocol: ProtocolVersion,
+    ) -> Result<(GodotTypeIndex, SerializeFlag), DecodeError> {
+        if bytes.len() < 4 {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes { needed: 4 },
+                0,
+            ));
+        }
+
+        let id = LittleEndian::read_u16(&bytes[0..2]);
+        let Some(type_idx) = GodotTypeIndex::from_id(id, protocol) else {
+            return Err(DecodeError::new(DecodeErrorKind::UnknownType(id as u32), 0));
+        };
+
+        let bits = LittleEndian::read_u16(&bytes[2..4]);
+        let flag = match (protocol, type_idx) {
+            // Godot 3 has no typed containers
+            (ProtocolVersion::Godot3, GodotTypeIndex::Array | GodotTypeIndex::Dictionary) => {
+                SerializeFlag::None
+            }
+            _ => SerializeFlag::from_bits(&type_idx, bits),
         };
-        let flag =
-            SerializeFlag::try_from(LittleEndian::read_u16(&bytes[2..4])).unwrap_or(SerializeFlag::None);
 
         Ok((type_idx, flag))
     }
 
+    /// Checks that there are enough bytes to decode a variant that has a fixed length
+    fn check_length(
+        bytes: &[u8],
+        length: usize,
+        type_idx: GodotTypeIndex,
+    ) -> Result<(), DecodeError> {
+        if bytes.len() < length {
+            return Err(DecodeError::at_type(
+                DecodeErrorKind::NotEnoughBytes { needed: length },
+                type_idx,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The bytes from the offset to the offset plus the length. Offsets can be given by the caller
+    /// so the end is checked for overflow rather than added
+    pub(crate) fn bytes_at(
+        bytes: &[u8],
+        offset: usize,
+        length: usize,
+    ) -> Result<&[u8], DecodeError> {
+        offset
+            .checked_add(length)
+            .and_then(|end| bytes.get(offset..end))
+            .ok_or_else(|| {
+                DecodeError::new(
+                    DecodeErrorKind::NotEnoughBytes {
+                        needed: offset.saturating_add(length),
+                    },
+                    offset,
+                )
+            })
+    }
+
+    /// Decodes a number of real components starting at the offset in bytes. The components are 64
+    /// bit floats when the flag is [Bit64](SerializeFlag::Bit64)
+    fn decode_reals(
+        bytes: &[u8],
+        offset: usize,
+        count: usize,
+        flag: &SerializeFlag,
+    ) -> Result<Vec<f64>, DecodeError> {
+        let component_size = if flag == &SerializeFlag::Bit64 { 8 } else { 4 };
+        if bytes.len().saturating_sub(offset) / component_size < count {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: offset.saturating_add(count * component_size),
+                },
+                offset,
+            ));
+        }
+
+        let mut values = Vec::with_capacity(count);
+        for i in 0..count {
+            let component = Self::decode_raw_float(bytes, offset + i * component_size, flag)?;
+            values.push(component.value);
+        }
+
+        Ok(values)
+    }
+
     /// Decodes bytes into it's respective Godot variant. This can fail if the bytes does not match
     /// Godot's serialization rules or it's an unsupported type.
-    pub fn decode_variant(bytes: &[u8]) -> anyhow::Result<Box<dyn GodotVariant + 'static>> {
-        if bytes.is_empty() {
-            return Err(anyhow!("Empty bytes"));
+    pub fn decode_variant(bytes: &[u8]) -> Result<Box<dyn GodotVariant + 'static>, DecodeError> {
+        Self::decode_variant_with_options(bytes, &DecodeOptions::default())
+    }
+
+    /// Decodes bytes into it's respective Godot variant using the options given, the options are
+    /// passed down to every variant inside of a container.
+    pub fn decode_variant_with_options(
+        bytes: &[u8],
+        options: &DecodeOptions,
+    ) -> Result<Box<dyn GodotVariant + 'static>, DecodeError> {
+        Self::decode_variant_consumed_with_options(bytes, options).map(|(variant, _)| variant)
+    }
+
+    /// Decodes a variant from the start of the bytes, returning the variant and the amount of bytes
+    /// it took up. The bytes after the variant are ignored, so this can be used to decode variants
+    /// that are sent one after another.
+    ///
+    /// The amount of bytes consumed is not always the
+    /// [byte length](GodotVariant::byte_length) of the variant. Godot can send a small integer as
+    /// 64 bits or a dictionary with the same key twice, which take up more bytes than the variant
+    /// is encoded back into
+    pub fn decode_variant_consumed(
+        bytes: &[u8],
+    ) -> Result<(Box<dyn GodotVariant + 'static>, usize), DecodeError> {
+        Self::decode_variant_consumed_with_options(bytes, &DecodeOptions::default())
+    }
+
+    /// Decodes a variant from the start of the bytes using the options given, returning the
+    /// variant and the amount of bytes it took up
+    pub fn decode_variant_consumed_with_options(
+        bytes: &[u8],
+        options: &DecodeOptions,
+    ) -> Result<(Box<dyn GodotVariant + 'static>, usize), DecodeError> {
+        Self::decode_variant_with_budget(bytes, options, &mut DecodeBudget::new(options.limits))
+    }
+
+    /// Decodes bytes into a variant and the amount of bytes it took up, counting what it allocates
+    /// against the budget. This will fail if the variant is nested deeper than the limit
+    fn decode_variant_with_budget(
+        bytes: &[u8],
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<DecodedVariant, DecodeError> {
+        let (type_idx, flag) = Self::get_type_and_flags_with_protocol(bytes, options.protocol)?;
+
+        if budget.depth >= budget.limits.max_depth {
+            return Err(DecodeError::at_type(DecodeErrorKind::TooDeep, type_idx));
+        }
+
+        let (variant, length) =
+            Self::decode_variant_of_type(bytes, type_idx, &flag, options, budget)
+                .map_err(|error| error.in_type(type_idx))?;
+        budget
+            .allocate(mem::size_of_val(&*variant), 0)
+            .map_err(|error| error.in_type(type_idx))?;
+
+        Ok((variant, length))
+    }
+
+    /// Decodes a variant inside of a container starting at the offset in bytes, returning the
+    /// variant and the amount of bytes it took up. The offset of an error in the variant is
+    /// counted from the start of the container
+    fn decode_nested_variant(
+        bytes: &[u8],
+        offset: usize,
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<DecodedVariant, DecodeError> {
+        // A nested variant needs at least its header
+        let Some(nested_bytes) = bytes.get(offset..) else {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: offset.saturating_add(4),
+                },
+                offset,
+            ));
+        };
+
+        budget.depth += 1;
+        let variant = Self::decode_variant_with_budget(nested_bytes, options, budget);
+        budget.depth -= 1;
+
+        variant.map_err(|error| error.offset_by(offset))
+    }
+
+    /// Decodes bytes into the variant of the type from its header and the amount of bytes it took
+    /// up
+    fn decode_variant_of_type(
+        bytes: &[u8],
+        type_idx: GodotTypeIndex,
+        flag: &SerializeFlag,
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<DecodedVariant, DecodeError> {
+        if let Some(decoded) = Self::decode_plain_variant(bytes, type_idx, flag, options, budget)? {
+            return Ok(decoded);
         }
 
-        let (type_idx, flag) = Self::get_type_and_flags(bytes)?;
+        // Only the variants that hold other variants are left
+        let decoded: DecodedVariant = match type_idx {
+            GodotTypeIndex::Object => {
+                let (object, length) =
+                    Self::decode_object_with_budget(bytes, flag, options, budget)?;
+                (Box::new(object), length)
+            }
+            GodotTypeIndex::Dictionary => {
+                let (dict, length) = Self::decode_dictionary_with_budget(bytes, options, budget)?;
+                (Box::new(dict), length)
+            }
+            _ => match flag {
+                SerializeFlag::TypedArray(_) => {
+                    let (array, length) =
+                        Self::decode_typed_array_with_budget(bytes, flag, options, budget)?;
+                    (Box::new(array), length)
+                }
+                _ => {
+                    let (array, length) = Self::decode_array_with_budget(bytes, options, budget)?;
+                    (Box::new(array), length)
+                }
+            },
+        };
+
+        Ok(decoded)
+    }
+
+    /// Decodes bytes into a variant that can't hold other variants and the amount of bytes it took
+    /// up, this is None for objects, dictionaries and arrays. These are kept apart so that each
+    /// nested container doesn't reserve stack space for every type
+    #[inline(never)]
+    fn decode_plain_variant(
+        bytes: &[u8],
+        type_idx: GodotTypeIndex,
+        flag: &SerializeFlag,
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<Option<DecodedVariant>, DecodeError> {
+        if let Some(element_size) = Self::packed_element_size(type_idx) {
+            Self::check_packed_limits(bytes, element_size, budget)?;
+        }
 
         let variant: Box<dyn GodotVariant> = match type_idx {
             GodotTypeIndex::Nil => Box::new(GodotNull),
-            GodotTypeIndex::Bool => Box::new(Self::decode_bool(bytes, &flag)?),
-            GodotTypeIndex::Integer => Box::new(Self::decode_int(bytes, &flag)?),
-            GodotTypeIndex::Float => Box::new(Self::decode_float(bytes, &flag)?),
-            GodotTypeIndex::String => Box::new(Self::decode_string(bytes)?),
-            GodotTypeIndex::Vector2 => Box::new(Self::decode_vector2(bytes)?),
-            GodotTypeIndex::Vector3 => Box::new(Self::decode_vector3(bytes)?),
-            GodotTypeIndex::Dictionary => Box::new(Self::decode_dictionary(bytes)?),
-            _ => return Err(anyhow!("Unsupported godot variant of type {:?}", type_idx)),
+            GodotTypeIndex::Bool => Box::new(Self::decode_bool(bytes, flag)?),
+            GodotTypeIndex::Integer => Box::new(Self::decode_int(bytes, flag)?),
+            GodotTypeIndex::Float => Box::new(Self::decode_float(bytes, flag)?),
+            GodotTypeIndex::String => Box::new(Self::decode_string_with_budget(bytes, budget)?),
+            GodotTypeIndex::StringName => {
+                Box::new(Self::decode_string_name_with_budget(bytes, budget)?)
+            }
+            GodotTypeIndex::NodePath => {
+                Box::new(Self::decode_node_path_with_budget(bytes, budget)?)
+            }
+            // Godot 3 only sends the header of a RID so there is no id to decode, the RID is kept
+            // with an id of 0 so that it is still a RID when it is encoded again
+            GodotTypeIndex::RID if options.protocol == ProtocolVersion::Godot3 => {
+                return Ok(Some((Box::new(GodotRid::new(0)), TYPE_PADDING as usize)));
+            }
+            GodotTypeIndex::RID => Box::new(Self::decode_rid(bytes)?),
+            GodotTypeIndex::Callable => Box::new(Self::decode_callable(bytes)?),
+            GodotTypeIndex::Signal => Box::new(Self::decode_signal_with_budget(bytes, budget)?),
+            GodotTypeIndex::Vector2 => Box::new(Self::decode_vector2(bytes, flag)?),
+            GodotTypeIndex::Vector3 => Box::new(Self::decode_vector3(bytes, flag)?),
+            GodotTypeIndex::Vector4 => Box::new(Self::decode_vector4(bytes, flag)?),
+            GodotTypeIndex::Vector2I => Box::new(Self::decode_vector2i(bytes)?),
+            GodotTypeIndex::Vector3I => Box::new(Self::decode_vector3i(bytes)?),
+            GodotTypeIndex::Vector4I => Box::new(Self::decode_vector4i(bytes)?),
+            GodotTypeIndex::Rect2 => Box::new(Self::decode_rect2(bytes, flag)?),
+            GodotTypeIndex::Rect2I => Box::new(Self::decode_rect2i(bytes)?),
+            GodotTypeIndex::Aabb => Box::new(Self::decode_aabb(bytes, flag)?),
+            GodotTypeIndex::Transform2D => Box::new(Self::decode_transform2d(bytes, flag)?),
+            GodotTypeIndex::Basis => Box::new(Self::decode_basis(bytes, flag)?),
+            GodotTypeIndex::Transform3D => Box::new(Self::decode_transform3d(bytes, flag)?),
+            GodotTypeIndex::Projection => Box::new(Self::decode_projection(bytes, flag)?),
+            GodotTypeIndex::Quaternion => Box::new(Self::decode_quaternion(bytes, flag)?),
+            GodotTypeIndex::Plane => Box::new(Self::decode_plane(bytes, flag)?),
+            GodotTypeIndex::Color => Box::new(Self::decode_color(bytes)?),
+            GodotTypeIndex::PackedByteArray => Box::new(Self::decode_packed_byte_array(bytes)?),
+            GodotTypeIndex::PackedInt32Array => Box::new(Self::decode_packed_int32_array(bytes)?),
+            GodotTypeIndex::PackedInt64Array => Box::new(Self::decode_packed_int64_array(bytes)?),
+            GodotTypeIndex::PackedFloat32Array => {
+                Box::new(Self::decode_packed_float32_array(bytes)?)
+            }
+            GodotTypeIndex::PackedFloat64Array => {
+                Box::new(Self::decode_packed_float64_array(bytes)?)
+            }
+            GodotTypeIndex::PackedStringArray => {
+                let (array, length) = Self::decode_packed_string_array_with_budget(bytes, budget)?;
+                return Ok(Some((Box::new(array), length)));
+            }
+            GodotTypeIndex::PackedVector2Array => {
+                Box::new(Self::decode_packed_vector2_array(bytes, flag)?)
+            }
+            GodotTypeIndex::PackedVector3Array => {
+                Box::new(Self::decode_packed_vector3_array(bytes, flag)?)
+            }
+            GodotTypeIndex::PackedVector4Array => {
+                Box::new(Self::decode_packed_vector4_array(bytes, flag)?)
+            }
+            GodotTypeIndex::PackedColorArray => Box::new(Self::decode_packed_color_array(bytes)?),
+            GodotTypeIndex::Object | GodotTypeIndex::Dictionary | GodotTypeIndex::Array => {
+                return Ok(None)
+            }
+        };
+
+        // Integers are encoded by their value, but a small integer can still be sent as 64 bits
+        let length = match type_idx {
+            GodotTypeIndex::Integer => TYPE_PADDING as usize + Self::int_size(flag),
+            _ => variant.byte_length(),
         };
 
-        Ok(variant)
+        Ok(Some((variant, length)))
     }
 }
@@ -0,0 +1,99 @@
+use std::mem;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::{node_path::GodotNodePath, GodotTypeIndex},
+};
+
+use super::{DecodeBudget, Decoder};
+
+/// Set on the name count to mark the node path as the new format
+const NEW_FORMAT_BIT: u32 = 0x80000000;
+/// Set in the node path flags when the path is absolute
+const ABSOLUTE_FLAG: u32 = 1;
+/// Set in the node path flags by older versions of godot which stored the property separately
+const PROPERTY_FLAG: u32 = 2;
+
+impl Decoder {
+    /// Decodes bytes into a Godot node path. Only the new node path format is supported
+    pub fn decode_node_path(bytes: &[u8]) -> Result<GodotNodePath, DecodeError> {
+        Self::decode_node_path_with_budget(bytes, &mut DecodeBudget::unlimited())
+    }
+
+    /// Decodes bytes into a Godot node path, counting the names against the budget
+    pub(crate) fn decode_node_path_with_budget(
+        bytes: &[u8],
+        budget: &mut DecodeBudget,
+    ) -> Result<GodotNodePath, DecodeError> {
+        if bytes.len() < 16 {
+            return Err(DecodeError::at_type(
+                DecodeErrorKind::NotEnoughBytes { needed: 16 },
+                GodotTypeIndex::NodePath,
+            ));
+        }
+
+        let name_count = LittleEndian::read_u32(&bytes[4..8]);
+        if name_count & NEW_FORMAT_BIT == 0 {
+            return Err(DecodeError::at_type(
+                DecodeErrorKind::OldNodePathFormat,
+                GodotTypeIndex::NodePath,
+            ));
+        }
+
+        let name_count = (name_count & !NEW_FORMAT_BIT) as usize;
+        let mut subname_count = LittleEndian::read_u32(&bytes[8..12]) as usize;
+        let flags = LittleEndian::read_u32(&bytes[12..16]);
+
+        // The property was stored as an extra subname
+        if flags & PROPERTY_FLAG != 0 {
+            subname_count += 1;
+        }
+
+        budget.check_container_length(name_count, 4)?;
+        budget.check_container_length(subname_count, 8)?;
+        let string_size = mem::size_of::<String>();
+        budget.allocate(name_count.saturating_mul(string_size), 4)?;
+        budget.allocate(subname_count.saturating_mul(string_size), 8)?;
+
+        let mut offset = 16;
+        let mut names = Vec::new();
+        for _ in 0..name_count {
+            let (name, length) = Self::decode_raw_string_with_budget(bytes, offset, budget)?;
+            names.push(name);
+            offset += length;
+        }
+
+        let mut subnames = Vec::new();
+        for _ in 0..subname_count {
+            let (subname, length) = Self::decode_raw_string_with_budget(bytes, offset, budget)?;
+            subnames.push(subname);
+            offset += length;
+        }
+
+        Ok(GodotNodePath::new(
+            names,
+            subnames,
+            flags & ABSOLUTE_FLAG != 0,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decoder::Decoder;
+
+    #[test]
+    fn decode_node_path() {
+        let bytes: &[u8] = &[
+            22, 0, 0, 0, 2, 0, 0, 128, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 46, 46, 0, 0, 6, 0, 0,
+            0, 80, 108, 97, 121, 101, 114, 0, 0, 8, 0, 0, 0, 112, 111, 115, 105, 116, 105, 111,
+            110,
+        ];
+        let node_path = Decoder::decode_node_path(bytes).unwrap();
+
+        assert_eq!(node_path.to_string(), "../Player:position");
+        assert!(!node_path.absolute);
+    }
+}
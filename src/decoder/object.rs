@@ -0,0 +1,232 @@
+use std::mem;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::{
+        object::{GodotCallable, GodotObject, GodotRid, GodotSignal},
+        variant::GodotVariant,
+        GodotTypeIndex, SerializeFlag,
+    },
+};
+
+use super::{DecodeBudget, DecodeOptions, Decoder};
+
+impl Decoder {
+    /// Decodes bytes into a Godot rid
+    pub fn decode_rid(bytes: &[u8]) -> Result<GodotRid, DecodeError> {
+        Self::check_length(bytes, 12, GodotTypeIndex::RID)?;
+
+        Ok(GodotRid::new(LittleEndian::read_u64(&bytes[4..12])))
+    }
+
+    /// Decodes bytes into a Godot callable. Godot doesn't serialize callables so this is always a
+    /// null callable
+    pub fn decode_callable(bytes: &[u8]) -> Result<GodotCallable, DecodeError> {
+        Self::check_length(bytes, 4, GodotTypeIndex::Callable)?;
+
+        Ok(GodotCallable)
+    }
+
+    /// Decodes bytes into a Godot signal
+    pub fn decode_signal(bytes: &[u8]) -> Result<GodotSignal, DecodeError> {
+        Self::decode_signal_with_budget(bytes, &mut DecodeBudget::unlimited())
+    }
+
+    /// Decodes bytes into a Godot signal, counting its name against the budget
+    pub(crate) fn decode_signal_with_budget(
+        bytes: &[u8],
+        budget: &mut DecodeBudget,
+    ) -> Result<GodotSignal, DecodeError> {
+        let (name, length) = Self::decode_raw_string_with_budget(bytes, 4, budget)?;
+
+        let offset = 4 + length;
+        let object_id = LittleEndian::read_u64(Self::bytes_at(bytes, offset, 8)?);
+
+        Ok(GodotSignal { name, object_id })
+    }
+
+    /// Decodes bytes into a Godot object. Objects sent as an instance id are always decoded, but
+    /// objects sent with their class name and properties will fail unless the options allow
+    /// objects
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Bytes from godot's var_to_bytes_with_objects
+    /// let bytes = /* Pretend we have valid bytes here */
+    ///
+    /// let variant = Decoder::decode_variant_with_options(&bytes, &DecodeOptions::with_objects())?;
+    /// let object = variant.as_var::<GodotObject>().unwrap();
+    ///
+    /// // Assuming the object has a health property
+    /// let health = object.get::<GodotInteger>("health");
+    /// ```
+    pub fn decode_object(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+        options: &DecodeOptions,
+    ) -> Result<GodotObject, DecodeError> {
+        Self::decode_object_with_budget(
+            bytes,
+            flag,
+            options,
+            &mut DecodeBudget::new(options.limits),
+        )
+        .map(|(object, _)| object)
+    }
+
+    /// Decodes bytes into a Godot object, counting its properties against the budget. Returns the
+    /// object and the amount of bytes it took up
+    pub(crate) fn decode_object_with_budget(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+        options: &DecodeOptions,
+        budget: &mut DecodeBudget,
+    ) -> Result<(GodotObject, usize), DecodeError> {
+        if let SerializeFlag::ObjectAsId = flag {
+            Self::check_length(bytes, 12, GodotTypeIndex::Object)?;
+
+            let object = GodotObject::from_id(LittleEndian::read_u64(&bytes[4..12]));
+            return Ok((object, 12));
+        }
+
+        if !options.allow_objects {
+            return Err(DecodeError::at_type(
+                DecodeErrorKind::ObjectsNotAllowed,
+                GodotTypeIndex::Object,
+            ));
+        }
+
+        let (class_name, length) = Self::decode_raw_string_with_budget(bytes, 4, budget)?;
+        let mut byte_pos = 4 + length;
+
+        // An empty class name is a null object and has no properties
+        if class_name.is_empty() {
+            return Ok((GodotObject::null(), byte_pos));
+        }
+
+        if bytes.len() < byte_pos + 4 {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: byte_pos + 4,
+                },
+                byte_pos,
+            ));
+        }
+
+        let property_count = LittleEndian::read_u32(&bytes[byte_pos..byte_pos + 4]) as usize;
+        budget.check_container_length(property_count, byte_pos)?;
+        budget.allocate(
+            property_count.saturating_mul(mem::size_of::<(String, Box<dyn GodotVariant>)>()),
+            byte_pos,
+        )?;
+        byte_pos += 4;
+
+        let mut object = GodotObject::new(&class_name);
+        for _ in 0..property_count {
+            let (name, length) = Self::decode_raw_string_with_budget(bytes, byte_pos, budget)?;
+            byte_pos += length;
+
+            let (value, length) = Self::decode_nested_variant(bytes, byte_pos, options, budget)?;
+            byte_pos += length;
+
+            object.properties.push((name, value));
+        }
+
+        Ok((object, byte_pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decoder::{DecodeOptions, Decoder},
+        encoder::{EncodeOptions, Encoder},
+        error::DecodeErrorKind,
+        types::{
+            object::{GodotObject, GodotRid, GodotSignal},
+            primitive::GodotInteger,
+            variant::AsVariant,
+            GodotTypeIndex, ProtocolVersion,
+        },
+    };
+
+    #[test]
+    fn decode_rid() {
+        let bytes: &[u8] = &[23, 0, 0, 0, 57, 48, 0, 0, 1, 0, 0, 0];
+        let rid = Decoder::decode_rid(bytes).unwrap();
+
+        assert_eq!(rid, GodotRid::new(4294979641));
+    }
+
+    #[test]
+    fn decode_godot3_rid() {
+        // Godot 3 only sends the header of a RID, it should still be a RID when encoded again
+        let id = GodotTypeIndex::RID.id(ProtocolVersion::Godot3).unwrap() as u8;
+        let bytes = [id, 0, 0, 0];
+        let options = DecodeOptions::with_protocol(ProtocolVersion::Godot3);
+        let (variant, length) =
+            Decoder::decode_variant_consumed_with_options(&bytes, &options).unwrap();
+
+        let rid = variant.as_var::<GodotRid>();
+        assert_eq!(
+            rid,
+            Some(&GodotRid::new(0)),
+            "Expected a RID but got {:?}",
+            variant
+        );
+        assert_eq!(length, 4);
+
+        let options = EncodeOptions::with_protocol(ProtocolVersion::Godot3);
+        let encoded = Encoder::encode_variant_with_options(&*variant, &options).unwrap();
+        assert_eq!(
+            bytes.to_vec(),
+            encoded,
+            "Expected {:?} but got {:?}",
+            bytes,
+            encoded
+        );
+    }
+
+    #[test]
+    fn decode_signal() {
+        let bytes: &[u8] = &[
+            26, 0, 0, 0, 3, 0, 0, 0, 104, 105, 116, 0, 42, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let signal = Decoder::decode_signal(bytes).unwrap();
+
+        assert_eq!(signal, GodotSignal::new("hit", 42));
+    }
+
+    #[test]
+    fn decode_object() {
+        let bytes: &[u8] = &[
+            24, 0, 0, 0, 4, 0, 0, 0, 78, 111, 100, 101, 1, 0, 0, 0, 2, 0, 0, 0, 104, 112, 0, 0, 2,
+            0, 0, 0, 100, 0, 0, 0,
+        ];
+
+        assert_eq!(
+            Decoder::decode_variant(bytes).unwrap_err().kind,
+            DecodeErrorKind::ObjectsNotAllowed,
+            "Full objects should not be decoded without being allowed"
+        );
+
+        let variant =
+            Decoder::decode_variant_with_options(bytes, &DecodeOptions::with_objects()).unwrap();
+        let object = variant.as_var::<GodotObject>().unwrap();
+
+        assert_eq!(object.class_name, "Node");
+        assert_eq!(object.get::<GodotInteger>("hp").unwrap().value, 100);
+    }
+
+    #[test]
+    fn decode_object_as_id() {
+        let bytes: &[u8] = &[24, 0, 1, 0, 42, 0, 0, 0, 0, 0, 0, 0];
+        let variant = Decoder::decode_variant(bytes).unwrap();
+        let object = variant.as_var::<GodotObject>().unwrap();
+
+        assert_eq!(object.id, Some(42));
+    }
+}
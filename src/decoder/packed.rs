@@ -0,0 +1,341 @@
+use std::mem;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::{
+        packed::{
+            GodotPackedByteArray, GodotPackedColorArray, GodotPackedFloat32Array,
+            GodotPackedFloat64Array, GodotPackedInt32Array, GodotPackedInt64Array,
+            GodotPackedStringArray, GodotPackedVector2Array, GodotPackedVector3Array,
+            GodotPackedVector4Array,
+        },
+        structures::{GodotColor, GodotVector2, GodotVector3, GodotVector4},
+        variant::GodotVariant,
+        GodotTypeIndex, SerializeFlag,
+    },
+};
+
+use super::{DecodeBudget, Decoder};
+
+impl Decoder {
+    /// Decodes bytes into a Godot packed byte array
+    pub fn decode_packed_byte_array(bytes: &[u8]) -> Result<GodotPackedByteArray, DecodeError> {
+        let data = Self::decode_packed_data(bytes, 1)?;
+        let array = GodotPackedByteArray::new(data.to_vec());
+
+        // The bytes are padded to a multiple of 4 like a string
+        if bytes.len() < array.byte_length() {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: array.byte_length(),
+                },
+                8,
+            ));
+        }
+
+        Ok(array)
+    }
+
+    /// Decodes bytes into a Godot packed 32 bit integer array
+    pub fn decode_packed_int32_array(bytes: &[u8]) -> Result<GodotPackedInt32Array, DecodeError> {
+        let data = Self::decode_packed_data(bytes, GodotPackedInt32Array::ELEMENT_SIZE)?;
+        let values = data
+            .chunks_exact(GodotPackedInt32Array::ELEMENT_SIZE)
+            .map(LittleEndian::read_i32)
+            .collect();
+
+        Ok(GodotPackedInt32Array::new(values))
+    }
+
+    /// Decodes bytes into a Godot packed 64 bit integer array
+    pub fn decode_packed_int64_array(bytes: &[u8]) -> Result<GodotPackedInt64Array, DecodeError> {
+        let data = Self::decode_packed_data(bytes, GodotPackedInt64Array::ELEMENT_SIZE)?;
+        let values = data
+            .chunks_exact(GodotPackedInt64Array::ELEMENT_SIZE)
+            .map(LittleEndian::read_i64)
+            .collect();
+
+        Ok(GodotPackedInt64Array::new(values))
+    }
+
+    /// Decodes bytes into a Godot packed 32 bit float array
+    pub fn decode_packed_float32_array(
+        bytes: &[u8],
+    ) -> Result<GodotPackedFloat32Array, DecodeError> {
+        let data = Self::decode_packed_data(bytes, GodotPackedFloat32Array::ELEMENT_SIZE)?;
+        let values = data
+            .chunks_exact(GodotPackedFloat32Array::ELEMENT_SIZE)
+            .map(LittleEndian::read_f32)
+            .collect();
+
+        Ok(GodotPackedFloat32Array::new(values))
+    }
+
+    /// Decodes bytes into a Godot packed 64 bit float array
+    pub fn decode_packed_float64_array(
+        bytes: &[u8],
+    ) -> Result<GodotPackedFloat64Array, DecodeError> {
+        let data = Self::decode_packed_data(bytes, GodotPackedFloat64Array::ELEMENT_SIZE)?;
+        let values = data
+            .chunks_exact(GodotPackedFloat64Array::ELEMENT_SIZE)
+            .map(LittleEndian::read_f64)
+            .collect();
+
+        Ok(GodotPackedFloat64Array::new(values))
+    }
+
+    /// Decodes bytes into a Godot packed string array. Each string is length prefixed and padded
+    /// to a multiple of 4, the null terminator godot writes is removed
+    pub fn decode_packed_string_array(bytes: &[u8]) -> Result<GodotPackedStringArray, DecodeError> {
+        Self::decode_packed_string_array_with_budget(bytes, &mut DecodeBudget::unlimited())
+            .map(|(array, _)| array)
+    }
+
+    /// Decodes bytes into a Godot packed string array, counting the array and each string against
+    /// the budget. Returns the array and the amount of bytes it took up, which can differ from its
+    /// encoded length when the strings were sent without a null terminator
+    pub(crate) fn decode_packed_string_array_with_budget(
+        bytes: &[u8],
+        budget: &mut DecodeBudget,
+    ) -> Result<(GodotPackedStringArray, usize), DecodeError> {
+        if bytes.len() < 8 {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes { needed: 8 },
+                4,
+            ));
+        }
+
+        let length = LittleEndian::read_u32(&bytes[4..8]) as usize;
+        budget.check_container_length(length, 4)?;
+        budget.allocate(length.saturating_mul(mem::size_of::<String>()), 4)?;
+
+        let mut values = Vec::new();
+        let mut byte_pos = 8;
+        for _ in 0..length {
+            let (mut value, string_length) =
+                Self::decode_raw_string_with_budget(bytes, byte_pos, budget)?;
+            byte_pos += string_length;
+
+            if value.ends_with('\0') {
+                value.pop();
+            }
+
+            values.push(value);
+        }
+
+        Ok((GodotPackedStringArray::new(values), byte_pos))
+    }
+
+    /// Decodes bytes into a Godot packed Vector2 array. The components are 64 bit floats when the
+    /// flag is [Bit64](SerializeFlag::Bit64)
+    pub fn decode_packed_vector2_array(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotPackedVector2Array, DecodeError> {
+        let components =
+            Self::decode_packed_reals(bytes, GodotPackedVector2Array::COMPONENTS, flag)?;
+        let values = components
+            .chunks_exact(GodotPackedVector2Array::COMPONENTS)
+            .map(|c| GodotVector2::new(c[0], c[1]))
+            .collect();
+
+        Ok(GodotPackedVector2Array {
+            values,
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Godot packed Vector3 array. The components are 64 bit floats when the
+    /// flag is [Bit64](SerializeFlag::Bit64)
+    pub fn decode_packed_vector3_array(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotPackedVector3Array, DecodeError> {
+        let components =
+            Self::decode_packed_reals(bytes, GodotPackedVector3Array::COMPONENTS, flag)?;
+        let values = components
+            .chunks_exact(GodotPackedVector3Array::COMPONENTS)
+            .map(|c| GodotVector3::new(c[0], c[1], c[2]))
+            .collect();
+
+        Ok(GodotPackedVector3Array {
+            values,
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Godot packed Vector4 array. The components are 64 bit floats when the
+    /// flag is [Bit64](SerializeFlag::Bit64)
+    pub fn decode_packed_vector4_array(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotPackedVector4Array, DecodeError> {
+        let components =
+            Self::decode_packed_reals(bytes, GodotPackedVector4Array::COMPONENTS, flag)?;
+        let values = components
+            .chunks_exact(GodotPackedVector4Array::COMPONENTS)
+            .map(|c| GodotVector4::new(c[0], c[1], c[2], c[3]))
+            .collect();
+
+        Ok(GodotPackedVector4Array {
+            values,
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Godot packed Color array
+    pub fn decode_packed_color_array(bytes: &[u8]) -> Result<GodotPackedColorArray, DecodeError> {
+        let components = Self::decode_packed_reals(bytes, 4, &SerializeFlag::None)?;
+        let values = components
+            .chunks_exact(4)
+            .map(|c| GodotColor::new(c[0] as f32, c[1] as f32, c[2] as f32, c[3] as f32))
+            .collect();
+
+        Ok(GodotPackedColorArray::new(values))
+    }
+
+    /// Decodes every float component of a packed array whose elements are made up of a number of
+    /// floats
+    fn decode_packed_reals(
+        bytes: &[u8],
+        components: usize,
+        flag: &SerializeFlag,
+    ) -> Result<Vec<f64>, DecodeError> {
+        let component_size = if flag == &SerializeFlag::Bit64 { 8 } else { 4 };
+        let data = Self::decode_packed_data(bytes, components * component_size)?;
+
+        Self::decode_reals(bytes, 8, data.len() / component_size, flag)
+    }
+
+    /// The size in memory of one element of a packed array type, this is None for packed string
+    /// arrays whose strings are counted as they are decoded and for types that aren't packed
+    /// arrays
+    pub(crate) fn packed_element_size(type_idx: GodotTypeIndex) -> Option<usize> {
+        let size = match type_idx {
+            GodotTypeIndex::PackedByteArray => mem::size_of::<u8>(),
+            GodotTypeIndex::PackedInt32Array => mem::size_of::<i32>(),
+            GodotTypeIndex::PackedInt64Array => mem::size_of::<i64>(),
+            GodotTypeIndex::PackedFloat32Array => mem::size_of::<f32>(),
+            GodotTypeIndex::PackedFloat64Array => mem::size_of::<f64>(),
+            GodotTypeIndex::PackedVector2Array => mem::size_of::<GodotVector2>(),
+            GodotTypeIndex::PackedVector3Array => mem::size_of::<GodotVector3>(),
+            GodotTypeIndex::PackedVector4Array => mem::size_of::<GodotVector4>(),
+            GodotTypeIndex::PackedColorArray => mem::size_of::<GodotColor>(),
+            _ => return None,
+        };
+
+        Some(size)
+    }
+
+    /// Checks the length of a packed array against the budget before its elements are decoded.
+    /// Bytes that are too short are left for the decoder to report
+    pub(crate) fn check_packed_limits(
+        bytes: &[u8],
+        element_size: usize,
+        budget: &mut DecodeBudget,
+    ) -> Result<(), DecodeError> {
+        if bytes.len() < 8 {
+            return Ok(());
+        }
+
+        let length = LittleEndian::read_u32(&bytes[4..8]) as usize;
+        budget.check_container_length(length, 4)?;
+        budget.allocate(length.saturating_mul(element_size), 4)
+    }
+
+    /// Reads the length of a packed array and returns the slice of bytes holding its elements.
+    /// This will fail if there are not enough bytes for every element
+    fn decode_packed_data(bytes: &[u8], element_size: usize) -> Result<&[u8], DecodeError> {
+        if bytes.len() < 8 {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes { needed: 8 },
+                4,
+            ));
+        }
+
+        let length = LittleEndian::read_u32(&bytes[4..8]) as usize;
+        let Some(data_length) = length.checked_mul(element_size) else {
+            return Err(DecodeError::new(DecodeErrorKind::LengthTooLarge(length), 4));
+        };
+
+        if bytes.len() - 8 < data_length {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: data_length.saturating_add(8),
+                },
+                8,
+            ));
+        }
+
+        Ok(&bytes[8..8 + data_length])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decoder::Decoder,
+        types::{
+            packed::{GodotPackedByteArray, GodotPackedStringArray, GodotPackedVector3Array},
+            structures::GodotVector3,
+            variant::{AsVariant, GodotVariant},
+        },
+    };
+
+    #[test]
+    fn decode_packed_byte_array() {
+        let bytes = [29, 0, 0, 0, 5, 0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 0];
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let array = variant.as_var::<GodotPackedByteArray>().unwrap();
+
+        assert_eq!(array.values, vec![1, 2, 3, 4, 5]);
+        assert_eq!(array.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_packed_int64_array() {
+        let bytes = [
+            31, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255,
+        ];
+        let array = Decoder::decode_packed_int64_array(&bytes).unwrap();
+
+        assert_eq!(array.values, vec![1, -1]);
+        assert_eq!(array.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_packed_array_too_short() {
+        let bytes = [30, 0, 0, 0, 255, 255, 255, 255, 1, 0, 0, 0];
+
+        assert!(Decoder::decode_packed_int32_array(&bytes).is_err());
+    }
+
+    #[test]
+    fn decode_packed_string_array() {
+        let bytes = [
+            34, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 104, 101, 121, 0, 6, 0, 0, 0, 119, 111, 114, 108,
+            100, 0, 0, 0,
+        ];
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let array = variant.as_var::<GodotPackedStringArray>().unwrap();
+
+        assert_eq!(array.values, vec!["hey".to_owned(), "world".to_owned()]);
+        assert_eq!(array.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_packed_vector3_array_double() {
+        let bytes = [
+            36, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 240, 63, 0, 0, 0, 0, 0, 0, 0, 64, 0, 0, 0,
+            0, 0, 0, 8, 192,
+        ];
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let array = variant.as_var::<GodotPackedVector3Array>().unwrap();
+
+        assert_eq!(array.values, vec![GodotVector3::new(1.0, 2.0, -3.0)]);
+        assert!(array.double_precision);
+        assert_eq!(array.byte_length(), bytes.len());
+    }
+}
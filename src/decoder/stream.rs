@@ -0,0 +1,286 @@
+use std::io::{ErrorKind, Read};
+
+use crate::{
+    error::{DecodeError, DecodeErrorKind, ReadError},
+    types::variant::GodotVariant,
+};
+
+use super::{DecodeOptions, Decoder};
+
+/// The most bytes asked for each time the reader is read from
+const READ_SIZE: usize = 8 * 1024;
+
+/// Decodes variants one after another out of a reader, such as a file or a tcp stream. Bytes are
+/// read as they are needed and kept until a whole variant has arrived, so the reader doesn't need
+/// to be buffered. This is made with [Decoder::from_reader]
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let file = File::open("save.bin")?;
+///
+/// // Every variant in the file is decoded until the end of the file
+/// for variant in Decoder::from_reader(file) {
+///     let variant = variant?;
+///     println!("{:?}", variant);
+/// }
+/// ```
+pub struct StreamDecoder<R> {
+    reader: R,
+    options: DecodeOptions,
+    buffer: Vec<u8>,
+    position: usize,
+    /// How many bytes the variant at the start of the buffer needs at least, it isn't decoded
+    /// again until the buffer holds that many
+    needed: usize,
+    eof: bool,
+    failed: bool,
+}
+
+impl Decoder {
+    /// Creates a decoder that reads variants out of a reader
+    pub fn from_reader<R: Read>(reader: R) -> StreamDecoder<R> {
+        Self::from_reader_with_options(reader, DecodeOptions::default())
+    }
+
+    /// Creates a decoder that reads variants out of a reader using the options given for every
+    /// variant. The limits apply to each variant on its own
+    pub fn from_reader_with_options<R: Read>(
+        reader: R,
+        options: DecodeOptions,
+    ) -> StreamDecoder<R> {
+        StreamDecoder {
+            reader,
+            options,
+            buffer: Vec::new(),
+            position: 0,
+            needed: 0,
+            eof: false,
+            failed: false,
+        }
+    }
+}
+
+impl<R: Read> StreamDecoder<R> {
+    /// Reads and decodes the next variant. Returns None once the reader has ended between two
+    /// variants, ending part way through a variant is a [NotEnoughBytes](DecodeErrorKind::NotEnoughBytes)
+    /// error.
+    ///
+    /// The reader is only read from when the buffer doesn't hold a whole variant, and a variant
+    /// that arrives a few bytes at a time is only decoded again once the bytes it was missing have
+    /// been read.
+    ///
+    /// Bytes that fail to decode can't be skipped over, so after a decode error this always returns
+    /// None. Errors from the reader leave the decoder as it was so reading can be tried again
+    pub fn decode_variant(&mut self) -> Result<Option<Box<dyn GodotVariant>>, ReadError> {
+        if self.failed {
+            return Ok(None);
+        }
+
+        loop {
+            if !self.buffer.is_empty() && (self.buffer.len() >= self.needed || self.eof) {
+                match Decoder::decode_variant_consumed_with_options(&self.buffer, &self.options) {
+                    Ok((variant, length)) => {
+                        self.buffer.drain(..length);
+                        self.position += length;
+                        self.needed = 0;
+
+                        return Ok(Some(variant));
+                    }
+                    // The rest of the variant hasn't been read yet
+                    Err(DecodeError {
+                        kind: DecodeErrorKind::NotEnoughBytes { needed },
+                        ..
+                    }) if !self.eof => self.needed = needed,
+                    Err(error) => {
+                        self.failed = true;
+                        return Err(error.offset_by(self.position).into());
+                    }
+                }
+            } else if self.buffer.is_empty() && self.eof {
+                return Ok(None);
+            }
+
+            if self.fill_buffer()? == 0 {
+                self.eof = true;
+            }
+        }
+    }
+
+    /// How many bytes of the stream have been decoded, this is where the next variant starts
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The bytes that have been read but not decoded yet
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Gets back the reader. Any bytes in the [buffer](Self::buffer) are lost
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads more bytes onto the end of the buffer with a single read, returning how many were
+    /// read. Waiting for more bytes than the reader has ready could wait forever on a connection
+    /// that is waiting for a reply
+    fn fill_buffer(&mut self) -> Result<usize, ReadError> {
+        let start = self.buffer.len();
+        self.buffer.resize(start + READ_SIZE, 0);
+
+        let read = loop {
+            match self.reader.read(&mut self.buffer[start..]) {
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                read => break read,
+            }
+        };
+
+        self.buffer.truncate(start + *read.as_ref().unwrap_or(&0));
+
+        Ok(read?)
+    }
+}
+
+impl<R: Read> Iterator for StreamDecoder<R> {
+    type Item = Result<Box<dyn GodotVariant>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decode_variant().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use crate::{
+        decoder::Decoder,
+        error::{DecodeErrorKind, ReadError},
+        types::{
+            packed::GodotPackedByteArray,
+            primitive::{GodotInteger, GodotString},
+            variant::AsVariant,
+        },
+    };
+
+    /// Gives out one byte for each read like a slow connection
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let Some((&byte, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+
+            buf[0] = byte;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    /// Counts how many times the reader is read from
+    struct CountingReader<R> {
+        reader: R,
+        reads: usize,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.reads += 1;
+            self.reader.read(buf)
+        }
+    }
+
+    /// Gives out each chunk in one read and then panics, like a connection that is waiting for a
+    /// reply and would block forever
+    struct ChunkReader<'a>(Vec<&'a [u8]>);
+
+    impl Read for ChunkReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            assert!(!self.0.is_empty(), "Read again after the last chunk");
+
+            let chunk = self.0.remove(0);
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn decode_stream() {
+        let bytes = [
+            2, 0, 0, 0, 42, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 104, 101, 108, 108, 111, 0, 0, 0,
+        ];
+        let mut decoder = Decoder::from_reader(ByteReader(&bytes));
+
+        let int = decoder.decode_variant().unwrap().unwrap();
+        assert_eq!(int.as_var::<GodotInteger>().unwrap().value, 42);
+        assert_eq!(decoder.position(), 8);
+
+        let string = decoder.decode_variant().unwrap().unwrap();
+        assert_eq!(string.as_var::<GodotString>().unwrap().value, "hello");
+        assert_eq!(decoder.position(), bytes.len());
+
+        assert!(decoder.decode_variant().unwrap().is_none());
+    }
+
+    #[test]
+    fn decode_stream_short_reads() {
+        // A packed byte array of 4096 bytes read one byte at a time
+        let mut bytes = vec![29, 0, 0, 0, 0, 16, 0, 0];
+        bytes.extend([7; 4096]);
+        let mut reader = CountingReader {
+            reader: ByteReader(&bytes),
+            reads: 0,
+        };
+        let mut decoder = Decoder::from_reader(&mut reader);
+
+        let array = decoder.decode_variant().unwrap().unwrap();
+        assert_eq!(
+            array.as_var::<GodotPackedByteArray>().unwrap().values.len(),
+            4096
+        );
+        assert_eq!(reader.reads, bytes.len(), "Expected one read for each byte");
+
+        // Once the length has arrived the array says how many bytes it needs, so the stream waits
+        // for all of them rather than decoding again after every byte
+        for end in 8..bytes.len() {
+            let error = Decoder::decode_variant_consumed(&bytes[..end]).unwrap_err();
+            let expected = DecodeErrorKind::NotEnoughBytes {
+                needed: bytes.len(),
+            };
+            assert_eq!(
+                expected, error.kind,
+                "Expected {:?} but got {:?}",
+                expected, error.kind
+            );
+        }
+    }
+
+    #[test]
+    fn decode_stream_waiting_for_reply() {
+        // A string that arrives in two parts, after which the sender waits for a reply
+        let bytes = [4, 0, 0, 0, 5, 0, 0, 0, 104, 101, 108, 108, 111, 0, 0, 0];
+        let mut decoder = Decoder::from_reader(ChunkReader(vec![&bytes[..10], &bytes[10..]]));
+
+        let string = decoder.decode_variant().unwrap().unwrap();
+        assert_eq!(string.as_var::<GodotString>().unwrap().value, "hello");
+        assert_eq!(decoder.position(), bytes.len());
+    }
+
+    #[test]
+    fn decode_stream_truncated() {
+        // An integer followed by a string that ends early
+        let bytes = [2, 0, 0, 0, 42, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 104, 101];
+
+        let variants = Decoder::from_reader(&bytes[..]).collect::<Vec<_>>();
+
+        assert_eq!(variants.len(), 2);
+        let Err(ReadError::Decode(error)) = &variants[1] else {
+            panic!("Expected a decode error but got {:?}", variants[1]);
+        };
+        assert!(matches!(error.kind, DecodeErrorKind::NotEnoughBytes { .. }));
+        // The length of the string, counted from the start of the stream
+        assert_eq!(error.offset, 12);
+    }
+}
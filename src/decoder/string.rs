@@ -1,39 +1,94 @@
-use anyhow::anyhow;
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::primitive::GodotString;
+use crate::{
+    error::{DecodeError, DecodeErrorKind},
+    types::primitive::{GodotString, GodotStringName},
+};
 
-use super::Decoder;
+use super::{DecodeBudget, Decoder};
 
 impl Decoder {
     /// Decodes bytes into a Godot string. This will fail if the bytes do not match Godot's
     /// serialization rules
-    pub fn decode_string(bytes: &[u8]) -> anyhow::Result<GodotString> {
-        if bytes.len() < 8 {
-            return Err(anyhow!("Not enough bytes for a string"));
-        }
+    pub fn decode_string(bytes: &[u8]) -> Result<GodotString, DecodeError> {
+        Self::decode_string_with_budget(bytes, &mut DecodeBudget::unlimited())
+    }
+
+    /// Decodes bytes into a Godot string, counting its bytes against the budget
+    pub(crate) fn decode_string_with_budget(
+        bytes: &[u8],
+        budget: &mut DecodeBudget,
+    ) -> Result<GodotString, DecodeError> {
+        let (string, _) = Self::decode_raw_string_with_budget(bytes, 4, budget)?;
+
+        Ok(GodotString { value: string })
+    }
+
+    /// Decodes bytes into a Godot StringName. This will fail if the bytes do not match Godot's
+    /// serialization rules
+    pub fn decode_string_name(bytes: &[u8]) -> Result<GodotStringName, DecodeError> {
+        Self::decode_string_name_with_budget(bytes, &mut DecodeBudget::unlimited())
+    }
 
-        let length = LittleEndian::read_u32(&bytes[4..8]) as usize;
+    /// Decodes bytes into a Godot StringName, counting its bytes against the budget
+    pub(crate) fn decode_string_name_with_budget(
+        bytes: &[u8],
+        budget: &mut DecodeBudget,
+    ) -> Result<GodotStringName, DecodeError> {
+        let (string, _) = Self::decode_raw_string_with_budget(bytes, 4, budget)?;
+
+        Ok(GodotStringName { value: string })
+    }
+
+    /// Decodes a length prefixed string that has no type header, starting at the offset in bytes.
+    /// Returns the string and the amount of bytes it took up including the length and padding
+    pub fn decode_raw_string(bytes: &[u8], offset: usize) -> Result<(String, usize), DecodeError> {
+        Self::decode_raw_string_with_budget(bytes, offset, &mut DecodeBudget::unlimited())
+    }
+
+    /// Decodes a length prefixed string starting at the offset in bytes, failing before the
+    /// string is allocated if it is over the limits of the budget
+    pub(crate) fn decode_raw_string_with_budget(
+        bytes: &[u8],
+        offset: usize,
+        budget: &mut DecodeBudget,
+    ) -> Result<(String, usize), DecodeError> {
+        let length = LittleEndian::read_u32(Self::bytes_at(bytes, offset, 4)?) as usize;
+        budget.check_string_length(length, offset)?;
         // Pad 4 bytes because godot
         let pad = (4 - (length % 4)) % 4;
 
-        let total_length = 8 + length + pad;
-        if bytes.len() < total_length {
-            return Err(anyhow!("Amount of bytes does not match string length"));
+        let Some(total_length) = length.checked_add(4 + pad) else {
+            return Err(DecodeError::new(
+                DecodeErrorKind::LengthTooLarge(length),
+                offset,
+            ));
+        };
+
+        if bytes.len() - offset < total_length {
+            return Err(DecodeError::new(
+                DecodeErrorKind::NotEnoughBytes {
+                    needed: offset.saturating_add(total_length),
+                },
+                offset,
+            ));
         }
 
-        let string = String::from_utf8(bytes[8..8 + length].to_vec())?;
+        budget.allocate(length, offset)?;
+        let string = String::from_utf8(bytes[offset + 4..offset + 4 + length].to_vec())
+            .map_err(|error| DecodeError::new(DecodeErrorKind::InvalidUtf8(error), offset))?;
 
-        Ok(GodotString {
-            value: string,
-            byte_size: total_length,
-        })
+        Ok((string, total_length))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::decoder::Decoder;
+    use crate::{
+        decoder::{DecodeLimits, DecodeOptions, Decoder},
+        error::DecodeErrorKind,
+        types::{variant::GodotVariant, GodotTypeIndex},
+    };
 
     #[test]
     fn decode_string() {
@@ -50,4 +105,43 @@ mod tests {
             value, string.value
         )
     }
+
+    #[test]
+    fn decode_string_name() {
+        let bytes: &[u8] = &[21, 0, 0, 0, 5, 0, 0, 0, 104, 101, 108, 108, 111, 0, 0, 0];
+        let string_name = Decoder::decode_string_name(bytes).unwrap();
+
+        assert_eq!(string_name.value, "hello");
+        assert_eq!(string_name.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_string_too_long() {
+        let bytes: &[u8] = &[4, 0, 0, 0, 5, 0, 0, 0, 104, 101, 108, 108, 111, 0, 0, 0];
+        let options = DecodeOptions::with_limits(DecodeLimits {
+            max_string_bytes: 4,
+            ..Default::default()
+        });
+
+        let error = Decoder::decode_variant_with_options(bytes, &options).unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            DecodeErrorKind::StringTooLong { length: 5, max: 4 }
+        );
+        assert_eq!(error.type_idx, Some(GodotTypeIndex::String));
+    }
+
+    #[test]
+    fn decode_raw_string_at_huge_offset() {
+        // Offsets are given by the caller, one next to the end of memory must not overflow
+        let bytes: &[u8] = &[4, 0, 0, 0, 5, 0, 0, 0, 104, 101, 108, 108, 111, 0, 0, 0];
+
+        let error = Decoder::decode_raw_string(bytes, usize::MAX - 1).unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            DecodeErrorKind::NotEnoughBytes { needed: usize::MAX }
+        );
+    }
 }
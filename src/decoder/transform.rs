@@ -0,0 +1,160 @@
+use crate::{
+    error::DecodeError,
+    types::{
+        structures::{GodotVector2, GodotVector3, GodotVector4},
+        transform::{
+            GodotBasis, GodotProjection, GodotQuaternion, GodotTransform2D, GodotTransform3D,
+        },
+        variant::GodotVariant,
+        SerializeFlag,
+    },
+};
+
+use super::Decoder;
+
+impl Decoder {
+    /// Decodes bytes into a Transform2D. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_transform2d(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotTransform2D, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 6, flag)?;
+
+        Ok(GodotTransform2D {
+            columns: [
+                GodotVector2::new(c[0], c[1]),
+                GodotVector2::new(c[2], c[3]),
+                GodotVector2::new(c[4], c[5]),
+            ],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Basis. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_basis(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotBasis, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 9, flag)?;
+
+        Ok(GodotBasis {
+            rows: [
+                GodotVector3::new(c[0], c[1], c[2]),
+                GodotVector3::new(c[3], c[4], c[5]),
+                GodotVector3::new(c[6], c[7], c[8]),
+            ],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Transform3D. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_transform3d(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotTransform3D, DecodeError> {
+        let basis = Self::decode_basis(bytes, flag)?;
+        let c = Self::decode_reals(bytes, basis.byte_length(), 3, flag)?;
+
+        Ok(GodotTransform3D {
+            basis,
+            origin: GodotVector3::new(c[0], c[1], c[2]),
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Projection. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_projection(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotProjection, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 16, flag)?;
+
+        Ok(GodotProjection {
+            columns: [
+                GodotVector4::new(c[0], c[1], c[2], c[3]),
+                GodotVector4::new(c[4], c[5], c[6], c[7]),
+                GodotVector4::new(c[8], c[9], c[10], c[11]),
+                GodotVector4::new(c[12], c[13], c[14], c[15]),
+            ],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a Quaternion. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_quaternion(
+        bytes: &[u8],
+        flag: &SerializeFlag,
+    ) -> Result<GodotQuaternion, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 4, flag)?;
+
+        Ok(GodotQuaternion {
+            x: c[0],
+            y: c[1],
+            z: c[2],
+            w: c[3],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decoder::Decoder,
+        types::{
+            structures::GodotVector3,
+            transform::{GodotBasis, GodotQuaternion, GodotTransform3D},
+            variant::{AsVariant, GodotVariant},
+            SerializeFlag,
+        },
+    };
+
+    #[test]
+    fn decode_transform3d() {
+        let bytes = [
+            18, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 128, 63, 0, 0, 128, 63, 0, 0, 0, 64, 0, 0, 64, 64,
+        ];
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let transform = variant.as_var::<GodotTransform3D>().unwrap();
+        let value = GodotTransform3D::new(GodotBasis::identity(), GodotVector3::new(1.0, 2.0, 3.0));
+
+        assert_eq!(
+            *transform, value,
+            "Expected value of {:?} but got {:?} instead",
+            value, transform
+        );
+    }
+
+    #[test]
+    fn decode_basis_double() {
+        let mut bytes = vec![17, 0, 1, 0];
+        for component in [2.0_f64, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0] {
+            bytes.extend_from_slice(&component.to_le_bytes());
+        }
+
+        let variant = Decoder::decode_variant(&bytes).unwrap();
+        let basis = variant.as_var::<GodotBasis>().unwrap();
+
+        assert_eq!(basis.column(1), GodotVector3::new(0.0, 2.0, 0.0));
+        assert!(basis.double_precision);
+        assert_eq!(basis.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn decode_quaternion() {
+        let bytes = [
+            15, 0, 0, 0, 0, 0, 0, 0, 243, 4, 53, 63, 0, 0, 0, 0, 243, 4, 53, 63,
+        ];
+        let quaternion = Decoder::decode_quaternion(&bytes, &SerializeFlag::None).unwrap();
+        let component = 0.70710677_f32 as f64;
+
+        assert_eq!(
+            quaternion,
+            GodotQuaternion::new(0.0, component, 0.0, component)
+        );
+        assert!((quaternion.length() - 1.0).abs() < f32::EPSILON as f64);
+    }
+}
@@ -1,28 +1,89 @@
-use crate::types::{
-    structures::{GodotVector2, GodotVector3},
-    SerializeFlag,
+use crate::{
+    error::DecodeError,
+    types::{
+        structures::{
+            GodotVector2, GodotVector2i, GodotVector3, GodotVector3i, GodotVector4, GodotVector4i,
+        },
+        GodotTypeIndex, SerializeFlag,
+    },
 };
 
 use super::Decoder;
 
 impl Decoder {
-    /// Decodes bytes into a vector 2. This will fail if the inner bytes can't be decoded into a
-    /// float
-    pub fn decode_vector2(bytes: &[u8]) -> anyhow::Result<GodotVector2> {
-        let x = Decoder::decode_raw_float(bytes, 4, &SerializeFlag::None)?.value as f32;
-        let y = Decoder::decode_raw_float(bytes, 8, &SerializeFlag::None)?.value as f32;
+    /// Decodes bytes into a vector 2. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_vector2(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotVector2, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 2, flag)?;
 
-        Ok(GodotVector2 { x, y })
+        Ok(GodotVector2 {
+            x: c[0],
+            y: c[1],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
     }
 
-    /// Decodes bytes into a vector 3. This will fail if the inner bytes can't be decoded into a
-    /// float
-    pub fn decode_vector3(bytes: &[u8]) -> anyhow::Result<GodotVector3> {
-        let x = Decoder::decode_raw_float(bytes, 4, &SerializeFlag::None)?.value as f32;
-        let y = Decoder::decode_raw_float(bytes, 8, &SerializeFlag::None)?.value as f32;
-        let z = Decoder::decode_raw_float(bytes, 12, &SerializeFlag::None)?.value as f32;
+    /// Decodes bytes into a vector 3. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_vector3(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotVector3, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 3, flag)?;
 
-        Ok(GodotVector3 { x, y, z })
+        Ok(GodotVector3 {
+            x: c[0],
+            y: c[1],
+            z: c[2],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a vector 4. The components are 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    pub fn decode_vector4(bytes: &[u8], flag: &SerializeFlag) -> Result<GodotVector4, DecodeError> {
+        let c = Self::decode_reals(bytes, 4, 4, flag)?;
+
+        Ok(GodotVector4 {
+            x: c[0],
+            y: c[1],
+            z: c[2],
+            w: c[3],
+            double_precision: flag == &SerializeFlag::Bit64,
+        })
+    }
+
+    /// Decodes bytes into a vector 2 of integers. This will fail if the inner bytes can't be
+    /// decoded into an integer
+    pub fn decode_vector2i(bytes: &[u8]) -> Result<GodotVector2i, DecodeError> {
+        Self::check_length(bytes, 12, GodotTypeIndex::Vector2I)?;
+
+        let x = Decoder::decode_raw_int(bytes, 4, &SerializeFlag::None)?.value as i32;
+        let y = Decoder::decode_raw_int(bytes, 8, &SerializeFlag::None)?.value as i32;
+
+        Ok(GodotVector2i { x, y })
+    }
+
+    /// Decodes bytes into a vector 3 of integers. This will fail if the inner bytes can't be
+    /// decoded into an integer
+    pub fn decode_vector3i(bytes: &[u8]) -> Result<GodotVector3i, DecodeError> {
+        Self::check_length(bytes, 16, GodotTypeIndex::Vector3I)?;
+
+        let x = Decoder::decode_raw_int(bytes, 4, &SerializeFlag::None)?.value as i32;
+        let y = Decoder::decode_raw_int(bytes, 8, &SerializeFlag::None)?.value as i32;
+        let z = Decoder::decode_raw_int(bytes, 12, &SerializeFlag::None)?.value as i32;
+
+        Ok(GodotVector3i { x, y, z })
+    }
+
+    /// Decodes bytes into a vector 4 of integers. This will fail if the inner bytes can't be
+    /// decoded into an integer
+    pub fn decode_vector4i(bytes: &[u8]) -> Result<GodotVector4i, DecodeError> {
+        Self::check_length(bytes, 20, GodotTypeIndex::Vector4I)?;
+
+        let x = Decoder::decode_raw_int(bytes, 4, &SerializeFlag::None)?.value as i32;
+        let y = Decoder::decode_raw_int(bytes, 8, &SerializeFlag::None)?.value as i32;
+        let z = Decoder::decode_raw_int(bytes, 12, &SerializeFlag::None)?.value as i32;
+        let w = Decoder::decode_raw_int(bytes, 16, &SerializeFlag::None)?.value as i32;
+
+        Ok(GodotVector4i { x, y, z, w })
     }
 }
 
@@ -30,18 +91,15 @@ impl Decoder {
 mod tests {
     use crate::{
         decoder::Decoder,
-        types::structures::{GodotVector2, GodotVector3},
+        types::structures::{GodotVector2, GodotVector3, GodotVector4},
     };
 
     #[test]
     fn decode_vector2() {
         let bytes = [5, 0, 0, 0, 0, 0, 134, 66, 0, 31, 94, 71];
-        let (_type, _flag) = Decoder::get_type_and_flags(&bytes).unwrap();
-        let vector2 = Decoder::decode_vector2(&bytes).unwrap();
-        let value = GodotVector2 {
-            x: 67.0,
-            y: 56863.0,
-        };
+        let (_type, flag) = Decoder::get_type_and_flags(&bytes).unwrap();
+        let vector2 = Decoder::decode_vector2(&bytes, &flag).unwrap();
+        let value = GodotVector2::new(67.0, 56863.0);
 
         assert_eq!(
             vector2, value,
@@ -53,13 +111,9 @@ mod tests {
     #[test]
     fn decode_vector3() {
         let bytes = [7, 0, 0, 0, 0, 0, 134, 66, 0, 31, 94, 71, 0, 179, 168, 199];
-        let (_type, _flag) = Decoder::get_type_and_flags(&bytes).unwrap();
-        let vector2 = Decoder::decode_vector3(&bytes).unwrap();
-        let value = GodotVector3 {
-            x: 67.0,
-            y: 56863.0,
-            z: -86374.0,
-        };
+        let (_type, flag) = Decoder::get_type_and_flags(&bytes).unwrap();
+        let vector2 = Decoder::decode_vector3(&bytes, &flag).unwrap();
+        let value = GodotVector3::new(67.0, 56863.0, -86374.0);
 
         assert_eq!(
             vector2, value,
@@ -67,4 +121,25 @@ mod tests {
             value, vector2
         );
     }
+
+    #[test]
+    fn decode_vector4() {
+        let bytes = [12, 0, 0, 0, 0, 0, 134, 66, 0, 31, 94, 71, 0, 179, 168, 199, 0, 0, 128, 63];
+        let (_type, flag) = Decoder::get_type_and_flags(&bytes).unwrap();
+        let vector4 = Decoder::decode_vector4(&bytes, &flag).unwrap();
+        let value = GodotVector4::new(67.0, 56863.0, -86374.0, 1.0);
+
+        assert_eq!(
+            vector4, value,
+            "Expected value of {:?} but got {:?} instead",
+            value, vector4
+        );
+    }
+
+    #[test]
+    fn decode_vector3i_too_short() {
+        let bytes = [10, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0];
+
+        assert!(Decoder::decode_vector3i(&bytes).is_err());
+    }
 }
@@ -0,0 +1,238 @@
+use std::io::Write;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{
+        structures::{GodotArray, GodotTypedArray},
+        ContainerType, ContainerTypeKind, GodotTypeIndex, ProtocolVersion, SerializeFlag,
+    },
+};
+
+use super::{EncodeOptions, Encoder};
+
+impl Encoder {
+    /// Encodes a Godot array into bytes. Each value in the array is encoded as its own variant,
+    /// so the values do not have to be the same type
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// [ "value", 42, Vector3(45, 2, 9) ]
+    /// ```
+    pub fn encode_array(array: &GodotArray) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_array_with_options(array, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot array into bytes using the options given for each value
+    pub fn encode_array_with_options(
+        array: &GodotArray,
+        options: &EncodeOptions,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_array_into_with_options(array, bytes, options))
+    }
+
+    /// Encodes a Godot array into a writer, each value is written as it is encoded
+    pub fn encode_array_into<W: Write + ?Sized>(
+        array: &GodotArray,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_array_into_with_options(array, writer, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot array into a writer using the options given for each value
+    pub fn encode_array_into_with_options<W: Write + ?Sized>(
+        array: &GodotArray,
+        writer: &mut W,
+        options: &EncodeOptions,
+    ) -> Result<(), EncodeError> {
+        writer.write_u16::<LittleEndian>(Self::type_id(GodotTypeIndex::Array, options)?)?;
+        writer.write_i16::<LittleEndian>(SerializeFlag::None.bits() as i16)?;
+        writer.write_i32::<LittleEndian>(array.values.len() as i32)?;
+
+        for value in array.values.iter() {
+            Encoder::encode_into_with_options(&**value, writer, options)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot typed array into bytes. The element type is written between the header and
+    /// the length of the array, this will fail if a value does not match the element type
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// Array[int]([ 1, 2, 3 ])
+    /// ```
+    pub fn encode_typed_array(array: &GodotTypedArray) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_typed_array_with_options(array, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot typed array into bytes using the options given for each value. Godot 3 has
+    /// no typed arrays so this will fail for godot 3
+    pub fn encode_typed_array_with_options(
+        array: &GodotTypedArray,
+        options: &EncodeOptions,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| {
+            Self::encode_typed_array_into_with_options(array, bytes, options)
+        })
+    }
+
+    /// Encodes a Godot typed array into a writer, each value is written as it is encoded
+    pub fn encode_typed_array_into<W: Write + ?Sized>(
+        array: &GodotTypedArray,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_typed_array_into_with_options(array, writer, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot typed array into a writer using the options given for each value
+    pub fn encode_typed_array_into_with_options<W: Write + ?Sized>(
+        array: &GodotTypedArray,
+        writer: &mut W,
+        options: &EncodeOptions,
+    ) -> Result<(), EncodeError> {
+        if options.protocol == ProtocolVersion::Godot3 {
+            return Err(EncodeError::UnsupportedByProtocol {
+                type_idx: GodotTypeIndex::Array,
+                protocol: options.protocol,
+            });
+        }
+
+        let flag = SerializeFlag::TypedArray(array.element_type.kind());
+
+        Self::write_header(writer, GodotTypeIndex::Array, &flag)?;
+        Self::encode_container_type_into(&array.element_type, writer)?;
+        writer.write_i32::<LittleEndian>(array.values.len() as i32)?;
+
+        for value in array.values.iter() {
+            array.element_type.check(&**value)?;
+
+            Encoder::encode_into_with_options(&**value, writer, options)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes the element type of a typed container. Builtin types are written as their type
+    /// index, while class names and scripts are written as strings
+    pub fn encode_container_type(container_type: &ContainerType) -> Vec<u8> {
+        match container_type.kind() {
+            ContainerTypeKind::None => Vec::new(),
+            ContainerTypeKind::Builtin => {
+                (container_type.builtin_type as u32).to_le_bytes().to_vec()
+            }
+            ContainerTypeKind::ClassName => {
+                Self::encode_raw_string(container_type.class_name.as_deref().unwrap_or_default())
+            }
+            ContainerTypeKind::Script => {
+                Self::encode_raw_string(container_type.script.as_deref().unwrap_or_default())
+            }
+        }
+    }
+
+    /// Encodes the element type of a typed container into a writer
+    pub fn encode_container_type_into<W: Write + ?Sized>(
+        container_type: &ContainerType,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        match container_type.kind() {
+            ContainerTypeKind::None => Ok(()),
+            ContainerTypeKind::Builtin => {
+                Ok(writer.write_u32::<LittleEndian>(container_type.builtin_type as u32)?)
+            }
+            ContainerTypeKind::ClassName => Self::encode_raw_string_into(
+                container_type.class_name.as_deref().unwrap_or_default(),
+                writer,
+            ),
+            ContainerTypeKind::Script => Self::encode_raw_string_into(
+                container_type.script.as_deref().unwrap_or_default(),
+                writer,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encoder::Encoder,
+        types::{
+            primitive::{GodotInteger, GodotString},
+            structures::{GodotArray, GodotTypedArray, GodotVector2},
+            ContainerType, GodotTypeIndex,
+        },
+    };
+
+    #[test]
+    fn encode_array() {
+        let expected_bytes = [
+            28, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 104, 105, 0,
+            0, 5, 0, 0, 0, 0, 0, 80, 66, 0, 0, 128, 63,
+        ]
+        .to_vec();
+
+        let mut array = GodotArray::new();
+        array.push(GodotInteger::new_from_i32(1));
+        array.push(GodotString::new("hi"));
+        array.push(GodotVector2::new(52.0, 1.0));
+
+        let bytes = Encoder::encode_array(&array).unwrap();
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_typed_array() {
+        let expected_bytes = [
+            28, 0, 1, 0, 2, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 5, 0, 0, 0, 2, 0, 0, 0, 6, 0, 0, 0,
+        ]
+        .to_vec();
+
+        let mut array = GodotTypedArray::new(ContainerType::builtin(GodotTypeIndex::Integer));
+        array.push(GodotInteger::new_from_i32(5)).unwrap();
+        array.push(GodotInteger::new_from_i32(6)).unwrap();
+
+        assert!(array.push(GodotString::new("seven")).is_err());
+
+        let bytes = Encoder::encode_typed_array(&array).unwrap();
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_array_into_vec() {
+        let mut array = GodotArray::new();
+        array.push(GodotInteger::new_from_i32(1));
+        array.push(GodotString::new("hi"));
+
+        let expected_bytes = Encoder::encode_array(&array).unwrap();
+        let mut bytes = vec![255; 4];
+        Encoder::encode_into_vec(&array, &mut bytes).unwrap();
+
+        assert_eq!(
+            expected_bytes,
+            bytes[4..],
+            "Expected {:?} but got {:?}",
+            expected_bytes,
+            bytes
+        );
+        assert_eq!(Encoder::encoded_len(&array).unwrap(), expected_bytes.len());
+
+        // A value that doesn't match the element type fails after the header has been written
+        let mut typed = GodotTypedArray::new(ContainerType::builtin(GodotTypeIndex::Integer));
+        typed.values.push(Box::new(GodotString::new("one")));
+
+        assert!(Encoder::encode_into_vec(&typed, &mut bytes).is_err());
+        assert_eq!(bytes.len(), 4 + expected_bytes.len());
+    }
+}
@@ -1,22 +1,40 @@
+use std::io::Write;
+
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::{primitive::GodotBool, SerializeFlag, GodotTypeIndex};
+use crate::{
+    error::EncodeError,
+    types::{primitive::GodotBool, GodotTypeIndex, SerializeFlag},
+};
 
 use super::Encoder;
 
 impl Encoder {
     /// Encodes a Godot bool into bytes
-    pub fn encode_bool(r#bool: &GodotBool) -> anyhow::Result<Vec<u8>> {
+    pub fn encode_bool(r#bool: &GodotBool) -> Result<Vec<u8>, EncodeError> {
         Ok(Self::encode_raw_bool(r#bool.value))
     }
 
+    /// Encodes a Godot bool into a writer
+    pub fn encode_bool_into<W: Write + ?Sized>(
+        r#bool: &GodotBool,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Ok(writer.write_all(&Self::raw_bool_bytes(r#bool.value))?)
+    }
+
     /// Encodes a bool into bytes
     pub fn encode_raw_bool(r#bool: bool) -> Vec<u8> {
-        let bytes: &mut [u8] = &mut [0; 8];
+        Self::raw_bool_bytes(r#bool).to_vec()
+    }
+
+    /// The bytes of a bool with its header
+    fn raw_bool_bytes(r#bool: bool) -> [u8; 8] {
+        let mut bytes = [0; 8];
         LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Bool as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None as i16);
+        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None.bits() as i16);
         LittleEndian::write_i32(&mut bytes[4..8], r#bool as i32);
 
-        bytes.to_vec()
+        bytes
     }
 }
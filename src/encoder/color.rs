@@ -0,0 +1,52 @@
+use std::io::Write;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{structures::GodotColor, GodotTypeIndex, SerializeFlag},
+};
+
+use super::Encoder;
+
+impl Encoder {
+    /// Encodes a Color into bytes. Colors are always written as 32 bit floats
+    pub fn encode_color(color: &GodotColor) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_color_into(color, bytes))
+    }
+
+    /// Encodes a Color into a writer
+    pub fn encode_color_into<W: Write + ?Sized>(
+        color: &GodotColor,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::Color, &SerializeFlag::None)?;
+        writer.write_f32::<LittleEndian>(color.r)?;
+        writer.write_f32::<LittleEndian>(color.g)?;
+        writer.write_f32::<LittleEndian>(color.b)?;
+        writer.write_f32::<LittleEndian>(color.a)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{encoder::Encoder, types::structures::GodotColor};
+
+    #[test]
+    fn encode_color() {
+        let expected_bytes = [
+            20, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 63, 0, 0, 0, 0, 0, 0, 128, 63,
+        ]
+        .to_vec();
+        let value = GodotColor::new(1.0, 0.5, 0.0, 1.0);
+        let bytes = Encoder::encode_color(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+}
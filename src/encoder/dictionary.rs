@@ -1,8 +1,16 @@
+use std::io::Write;
+
 use byteorder::{LittleEndian, WriteBytesExt};
 
-use crate::types::{structures::GodotDictionary, SerializeFlag, GodotTypeIndex};
+use crate::{
+    error::EncodeError,
+    types::{
+        structures::GodotDictionary, ContainerType, ContainerTypeKind, GodotTypeIndex,
+        ProtocolVersion, SerializeFlag,
+    },
+};
 
-use super::Encoder;
+use super::{EncodeOptions, Encoder};
 
 impl Encoder {
     /// Encodes a Godot dictionary into bytes. A godot dictionary consists of key value pairs.
@@ -14,34 +22,83 @@ impl Encoder {
     /// ```json
     /// { "key": "value", "key2": 42, Vector3(45, 2, 9): 9529 }
     /// ```
-    pub fn encode_dictionary(dictionary: &GodotDictionary) -> anyhow::Result<Vec<u8>> {
-        let mut bytes: Vec<u8> = Vec::new();
+    ///
+    /// Typed dictionaries write their key and value types between the header and the length, this
+    /// will fail if an entry does not match those types
+    pub fn encode_dictionary(dictionary: &GodotDictionary) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_dictionary_with_options(dictionary, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot dictionary into bytes using the options given for each key and value.
+    /// Godot 3 has no typed dictionaries so this will fail for a typed dictionary in godot 3
+    pub fn encode_dictionary_with_options(
+        dictionary: &GodotDictionary,
+        options: &EncodeOptions,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| {
+            Self::encode_dictionary_into_with_options(dictionary, bytes, options)
+        })
+    }
+
+    /// Encodes a Godot dictionary into a writer, each key and value is written as it is encoded
+    pub fn encode_dictionary_into<W: Write + ?Sized>(
+        dictionary: &GodotDictionary,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_dictionary_into_with_options(dictionary, writer, &EncodeOptions::default())
+    }
 
+    /// Encodes a Godot dictionary into a writer using the options given for each key and value
+    pub fn encode_dictionary_into_with_options<W: Write + ?Sized>(
+        dictionary: &GodotDictionary,
+        writer: &mut W,
+        options: &EncodeOptions,
+    ) -> Result<(), EncodeError> {
         let iterator = dictionary.map.iter();
         let length = iterator.len();
 
-        bytes.write_i16::<LittleEndian>(GodotTypeIndex::Dictionary as i16)?;
-        bytes.write_i16::<LittleEndian>(SerializeFlag::None as i16)?;
-        bytes.write_i32::<LittleEndian>(length as i32)?;
+        let untyped = ContainerType::builtin(GodotTypeIndex::Nil);
+        let key_type = dictionary.key_type.as_ref().unwrap_or(&untyped);
+        let value_type = dictionary.value_type.as_ref().unwrap_or(&untyped);
+        let flag = match (key_type.kind(), value_type.kind()) {
+            (ContainerTypeKind::None, ContainerTypeKind::None) => SerializeFlag::None,
+            (key, value) => SerializeFlag::TypedDictionary { key, value },
+        };
+
+        if flag != SerializeFlag::None && options.protocol == ProtocolVersion::Godot3 {
+            return Err(EncodeError::UnsupportedByProtocol {
+                type_idx: GodotTypeIndex::Dictionary,
+                protocol: options.protocol,
+            });
+        }
+
+        writer.write_u16::<LittleEndian>(Self::type_id(GodotTypeIndex::Dictionary, options)?)?;
+        writer.write_i16::<LittleEndian>(flag.bits() as i16)?;
+        Encoder::encode_container_type_into(key_type, writer)?;
+        Encoder::encode_container_type_into(value_type, writer)?;
+        writer.write_i32::<LittleEndian>(length as i32)?;
 
         for (key, value) in iterator {
-            let mut key_bytes = Encoder::encode_variant(&**key)?;
-            bytes.append(&mut key_bytes);
-            let mut value_bytes = Encoder::encode_variant(&**value)?;
-            bytes.append(&mut value_bytes);
+            dictionary.validate_entry(&**key, &**value)?;
+
+            Encoder::encode_into_with_options(&**key, writer, options)?;
+            Encoder::encode_into_with_options(&**value, writer, options)?;
         }
 
-        Ok(bytes)
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        encoder::Encoder,
+        encoder::{EncodeOptions, Encoder},
         types::{
-            primitive::{GodotInteger, GodotString},
-            structures::{GodotDictionary, GodotVector3},
+            object::GodotObject,
+            primitive::{GodotFloat, GodotInteger, GodotString, GodotStringName},
+            structures::{GodotArray, GodotDictionary, GodotTypedArray, GodotVector3},
+            variant::GodotVariant,
+            ContainerType, GodotTypeIndex, ProtocolVersion,
         },
     };
 
@@ -58,6 +115,38 @@ mod test {
         dict.insert(GodotString::new("position"), GodotVector3::new(0.52, 502.0, 68.0));
         dict.insert(GodotString::new("id"), GodotInteger::new_from_i32(693));
 
+        // The dictionary and vector ids are from godot 3
+        let options = EncodeOptions::with_protocol(ProtocolVersion::Godot3);
+        let bytes = Encoder::encode_dictionary_with_options(&dict, &options).unwrap();
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_typed_dictionary() {
+        let expected_bytes = [
+            27, 0, 5, 0, 4, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 104, 112, 0,
+            0, 2, 0, 0, 0, 100, 0, 0, 0,
+        ]
+        .to_vec();
+
+        let mut dict = GodotDictionary::new_typed(
+            Some(ContainerType::builtin(GodotTypeIndex::String)),
+            Some(ContainerType::builtin(GodotTypeIndex::Integer)),
+        );
+        dict.try_insert(GodotString::new("hp"), GodotInteger::new_from_i32(100))
+            .unwrap();
+
+        assert!(dict
+            .try_insert(
+                GodotString::new("position"),
+                GodotVector3::new(0.0, 0.0, 0.0)
+            )
+            .is_err());
+
         let bytes = Encoder::encode_dictionary(&dict).unwrap();
         assert_eq!(
             expected_bytes, bytes,
@@ -65,4 +154,37 @@ mod test {
             expected_bytes, bytes
         );
     }
+
+    #[test]
+    fn encoded_dictionary_length() {
+        let mut array = GodotArray::new();
+        array.push(GodotString::new("hi"));
+        array.push(GodotInteger::new_from_i64(i64::MAX));
+
+        let mut typed = GodotTypedArray::new(ContainerType::class_name("Node"));
+        typed.push(GodotObject::from_id(1)).unwrap();
+
+        let mut dict = GodotDictionary::new();
+        dict.insert(GodotString::new("array"), array);
+        dict.insert(GodotStringName::new("typed"), typed);
+        dict.insert(GodotInteger::new_from_i32(1), GodotFloat::new_from_f64(0.5));
+
+        let bytes = Encoder::encode_dictionary(&dict).unwrap();
+        assert_eq!(dict.byte_length(), bytes.len());
+
+        // The length follows the fields when they are changed
+        let mut string = GodotString::new("a");
+        string.value.push_str("bcde");
+        let bytes = Encoder::encode_string(&string).unwrap();
+        assert_eq!(string.byte_length(), bytes.len());
+    }
+
+    #[test]
+    fn encode_godot3_typed_dictionary() {
+        let dict =
+            GodotDictionary::new_typed(Some(ContainerType::builtin(GodotTypeIndex::String)), None);
+        let options = EncodeOptions::with_protocol(ProtocolVersion::Godot3);
+
+        assert!(Encoder::encode_dictionary_with_options(&dict, &options).is_err());
+    }
 }
@@ -1,43 +1,64 @@
 use core::f32;
+use std::io::Write;
 
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::{
-    primitive::GodotFloat, variant::GodotVariant, SerializeFlag, GodotTypeIndex, TYPE_PADDING,
+use crate::{
+    error::EncodeError,
+    types::{primitive::GodotFloat, GodotTypeIndex, SerializeFlag},
 };
 
 use super::Encoder;
 
 impl Encoder {
-    /// Encodes a Godot float into bytes. A Godot float will be encoded into its respective
-    /// sizes based on the float. If the byte length of the float is over the 
-    /// [32 bit size](GodotFloat::BIT_32_SIZE)
-    pub fn encode_float(float: &GodotFloat) -> anyhow::Result<Vec<u8>> {
-        if float.byte_length() > TYPE_PADDING as usize + GodotFloat::BIT_32_SIZE {
-            return Ok(Self::encode_f64(float.value));
+    /// Encodes a Godot float into bytes. A Godot float will be encoded as a 64 bit float when it
+    /// has [double precision](GodotFloat::double_precision)
+    pub fn encode_float(float: &GodotFloat) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_float_into(float, bytes))
+    }
+
+    /// Encodes a Godot float into a writer, as a 64 bit float when it was made as one
+    pub fn encode_float_into<W: Write + ?Sized>(
+        float: &GodotFloat,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        if float.double_precision {
+            writer.write_all(&Self::f64_bytes(float.value))?;
+        } else {
+            writer.write_all(&Self::f32_bytes(float.value as f32))?;
         }
 
-        Ok(Self::encode_f32(float.value as f32))
+        Ok(())
     }
 
     /// Encodes a 32 bit float into bytes
     pub fn encode_f32(i: f32) -> Vec<u8> {
-        let bytes: &mut [u8] = &mut [0; 8];
+        Self::f32_bytes(i).to_vec()
+    }
+
+    /// Encodes a 64 bit float into bytes
+    pub fn encode_f64(i: f64) -> Vec<u8> {
+        Self::f64_bytes(i).to_vec()
+    }
+
+    /// The bytes of a 32 bit float with its header
+    fn f32_bytes(i: f32) -> [u8; 8] {
+        let mut bytes = [0; 8];
         LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Float as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None as i16);
+        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None.bits() as i16);
         LittleEndian::write_f32(&mut bytes[4..8], i);
 
-        bytes.to_vec()
+        bytes
     }
 
-    /// Encodes a 64 bit float into bytes
-    pub fn encode_f64(i: f64) -> Vec<u8> {
-        let bytes: &mut [u8] = &mut [0; 12];
+    /// The bytes of a 64 bit float with its header
+    fn f64_bytes(i: f64) -> [u8; 12] {
+        let mut bytes = [0; 12];
         LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Float as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::Bit64 as i16);
+        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::Bit64.bits() as i16);
         LittleEndian::write_f64(&mut bytes[4..12], i);
 
-        bytes.to_vec()
+        bytes
     }
 }
 
@@ -0,0 +1,143 @@
+use std::io::Write;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{
+        geometry::{GodotAabb, GodotPlane, GodotRect2, GodotRect2i},
+        GodotTypeIndex, SerializeFlag,
+    },
+};
+
+use super::Encoder;
+
+impl Encoder {
+    /// Encodes a Rect2 into bytes, the position is written before the size
+    pub fn encode_rect2(rect2: &GodotRect2) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_rect2_into(rect2, bytes))
+    }
+
+    /// Encodes a Rect2 into a writer
+    pub fn encode_rect2_into<W: Write + ?Sized>(
+        rect2: &GodotRect2,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Rect2,
+            &[
+                rect2.position.x,
+                rect2.position.y,
+                rect2.size.x,
+                rect2.size.y,
+            ],
+            rect2.double_precision,
+        )
+    }
+
+    /// Encodes a Rect2i into bytes, the position is written before the size
+    pub fn encode_rect2i(rect2i: &GodotRect2i) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_rect2i_into(rect2i, bytes))
+    }
+
+    /// Encodes a Rect2i into a writer
+    pub fn encode_rect2i_into<W: Write + ?Sized>(
+        rect2i: &GodotRect2i,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::Rect2I, &SerializeFlag::None)?;
+        writer.write_i32::<LittleEndian>(rect2i.position.x)?;
+        writer.write_i32::<LittleEndian>(rect2i.position.y)?;
+        writer.write_i32::<LittleEndian>(rect2i.size.x)?;
+        writer.write_i32::<LittleEndian>(rect2i.size.y)?;
+
+        Ok(())
+    }
+
+    /// Encodes an AABB into bytes, the position is written before the size
+    pub fn encode_aabb(aabb: &GodotAabb) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_aabb_into(aabb, bytes))
+    }
+
+    /// Encodes an AABB into a writer
+    pub fn encode_aabb_into<W: Write + ?Sized>(
+        aabb: &GodotAabb,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Aabb,
+            &[
+                aabb.position.x,
+                aabb.position.y,
+                aabb.position.z,
+                aabb.size.x,
+                aabb.size.y,
+                aabb.size.z,
+            ],
+            aabb.double_precision,
+        )
+    }
+
+    /// Encodes a Plane into bytes, the normal is written before the distance
+    pub fn encode_plane(plane: &GodotPlane) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_plane_into(plane, bytes))
+    }
+
+    /// Encodes a Plane into a writer
+    pub fn encode_plane_into<W: Write + ?Sized>(
+        plane: &GodotPlane,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Plane,
+            &[plane.normal.x, plane.normal.y, plane.normal.z, plane.d],
+            plane.double_precision,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encoder::Encoder,
+        types::{
+            geometry::{GodotRect2, GodotRect2i},
+            structures::{GodotVector2, GodotVector2i},
+        },
+    };
+
+    #[test]
+    fn encode_rect2() {
+        let expected_bytes = [
+            7, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 64, 0, 0, 32, 65, 0, 0, 160, 64,
+        ]
+        .to_vec();
+        let value = GodotRect2::new(GodotVector2::new(1.0, 2.0), GodotVector2::new(10.0, 5.0));
+        let bytes = Encoder::encode_rect2(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_rect2i() {
+        let expected_bytes = [
+            8, 0, 0, 0, 255, 255, 255, 255, 2, 0, 0, 0, 16, 0, 0, 0, 32, 0, 0, 0,
+        ]
+        .to_vec();
+        let value = GodotRect2i::new(GodotVector2i::new(-1, 2), GodotVector2i::new(16, 32));
+        let bytes = Encoder::encode_rect2i(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+}
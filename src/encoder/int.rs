@@ -1,39 +1,66 @@
+use std::io::Write;
+
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::types::{primitive::GodotInteger, SerializeFlag, GodotTypeIndex};
+use crate::{
+    error::EncodeError,
+    types::{primitive::GodotInteger, GodotTypeIndex, SerializeFlag},
+};
 
 use super::Encoder;
 
 impl Encoder {
     /// Encodes a Godot integer into bytes. A Godot integer will be encoded into its respective
-    /// sizes based on the integer. If the value is over the [32 bit max value](i32::MAX) it will
-    /// be encoded as a 64 bit integer
-    pub fn encode_int(int: &GodotInteger) -> anyhow::Result<Vec<u8>> {
-        if int.value > i32::MAX as i64 || int.value < i32::MIN as i64 {
-            return Ok(Self::encode_int64(int.value));
+    /// sizes based on the integer. If the value is over the [32 bit max value](i32::MAX) or the
+    /// integer is [forced to 64 bits](GodotInteger::force_64_bit) it will be encoded as a 64 bit
+    /// integer
+    pub fn encode_int(int: &GodotInteger) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_int_into(int, bytes))
+    }
+
+    /// Encodes a Godot integer into a writer, as a 64 bit integer when it doesn't fit in 32 bits or
+    /// is forced to 64 bits
+    pub fn encode_int_into<W: Write + ?Sized>(
+        int: &GodotInteger,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        if int.is_64_bit() {
+            writer.write_all(&Self::int64_bytes(int.value))?;
+        } else {
+            writer.write_all(&Self::int32_bytes(int.value as i32))?;
         }
 
-        Ok(Self::encode_int32(int.value as i32))
+        Ok(())
     }
 
     /// Encodes a 32 bit integer into bytes
     pub fn encode_int32(i: i32) -> Vec<u8> {
-        let bytes: &mut [u8] = &mut [0; 8];
+        Self::int32_bytes(i).to_vec()
+    }
+
+    /// Encodes a 64 bit integer into bytes
+    pub fn encode_int64(i: i64) -> Vec<u8> {
+        Self::int64_bytes(i).to_vec()
+    }
+
+    /// The bytes of a 32 bit integer with its header
+    fn int32_bytes(i: i32) -> [u8; 8] {
+        let mut bytes = [0; 8];
         LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Integer as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None as i16);
+        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None.bits() as i16);
         LittleEndian::write_i32(&mut bytes[4..8], i);
 
-        bytes.to_vec()
+        bytes
     }
 
-    /// Encodes a 64 bit integer into bytes
-    pub fn encode_int64(i: i64) -> Vec<u8> {
-        let bytes: &mut [u8] = &mut [0; 12];
+    /// The bytes of a 64 bit integer with its header
+    fn int64_bytes(i: i64) -> [u8; 12] {
+        let mut bytes = [0; 12];
         LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Integer as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::Bit64 as i16);
+        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::Bit64.bits() as i16);
         LittleEndian::write_i64(&mut bytes[4..12], i);
 
-        bytes.to_vec()
+        bytes
     }
 }
 
@@ -66,4 +93,17 @@ mod tests {
             expected_bytes, bytes
         );
     }
+
+    #[test]
+    fn encode_forced_int64() {
+        let expected_bytes = [2, 0, 1, 0, 7, 0, 0, 0, 0, 0, 0, 0].to_vec();
+        let value = GodotInteger::new_64_bit(7);
+        let bytes = Encoder::encode_int(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
 }
@@ -1,55 +1,411 @@
-use anyhow::anyhow;
+use std::io::Write;
 
-use crate::types::{
-    primitive::{GodotBool, GodotFloat, GodotInteger, GodotString},
-    structures::{GodotDictionary, GodotVector2, GodotVector3},
-    variant::{AsVariant, GodotVariant},
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use self::writer::{ByteCounter, HeaderWriter};
+use crate::{
+    error::EncodeError,
+    types::{
+        geometry::{GodotAabb, GodotPlane, GodotRect2, GodotRect2i},
+        node_path::GodotNodePath,
+        object::{GodotCallable, GodotObject, GodotRid, GodotSignal},
+        packed::{
+            GodotPackedByteArray, GodotPackedColorArray, GodotPackedFloat32Array,
+            GodotPackedFloat64Array, GodotPackedInt32Array, GodotPackedInt64Array,
+            GodotPackedStringArray, GodotPackedVector2Array, GodotPackedVector3Array,
+            GodotPackedVector4Array,
+        },
+        primitive::{GodotBool, GodotFloat, GodotInteger, GodotNull, GodotString, GodotStringName},
+        structures::{
+            GodotArray, GodotColor, GodotDictionary, GodotTypedArray, GodotVector2, GodotVector2i,
+            GodotVector3, GodotVector3i, GodotVector4, GodotVector4i,
+        },
+        transform::{
+            GodotBasis, GodotProjection, GodotQuaternion, GodotTransform2D, GodotTransform3D,
+        },
+        variant::{AsVariant, GodotVariant},
+        GodotTypeIndex, ProtocolVersion, SerializeFlag,
+    },
 };
 
+pub mod array;
+pub mod color;
 pub mod dictionary;
 pub mod float;
+pub mod geometry;
 pub mod int;
+pub mod nil;
+pub mod node_path;
+pub mod object;
+pub mod packed;
 pub mod string;
+pub mod transform;
 pub mod vector;
 pub mod bool;
 
+mod writer;
+
 /// Encodes a variant from its type into bytes
 pub struct Encoder;
 
+/// Changes how the encoder writes variants. The default options match godot 4's `var_to_bytes`
+#[derive(Debug, Clone, Default)]
+pub struct EncodeOptions {
+    /// The version of godot the bytes will be sent to
+    pub protocol: ProtocolVersion,
+}
+
+impl EncodeOptions {
+    /// The default options for bytes sent to a version of godot
+    pub fn with_protocol(protocol: ProtocolVersion) -> Self {
+        Self { protocol }
+    }
+}
+
 impl Encoder {
     /// Takes in a Godot variant and determines how to encode it based on its type
-    pub fn encode_variant(variant: &dyn GodotVariant) -> anyhow::Result<Vec<u8>> {
+    pub fn encode_variant(variant: &dyn GodotVariant) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_variant_with_options(variant, &EncodeOptions::default())
+    }
+
+    /// Takes in a Godot variant and encodes it using the options given, the options are passed
+    /// down to every variant inside of a container. This will fail if the variant does not exist
+    /// in the protocol version
+    pub fn encode_variant_with_options(
+        variant: &dyn GodotVariant,
+        options: &EncodeOptions,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let mut bytes = Vec::new();
+        Self::encode_into_with_options(variant, &mut bytes, options)?;
+
+        Ok(bytes)
+    }
+
+    /// Encodes a Godot variant straight into a writer, values inside of containers are written
+    /// as they are encoded without being collected first. If this fails part of the variant may
+    /// have already been written
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+    ///
+    /// Encoder::encode_into(&world_state, &mut stream)?;
+    /// ```
+    pub fn encode_into<W: Write + ?Sized>(
+        variant: &dyn GodotVariant,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_into_with_options(variant, writer, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot variant straight into a writer using the options given
+    pub fn encode_into_with_options<W: Write + ?Sized>(
+        variant: &dyn GodotVariant,
+        writer: &mut W,
+        options: &EncodeOptions,
+    ) -> Result<(), EncodeError> {
+        let type_idx = variant.type_index();
+        let id = Self::type_id(type_idx, options)?;
+
+        if let Some(object) = variant.as_var::<GodotObject>() {
+            return Self::encode_object_into_with_options(object, writer, options);
+        }
+
+        if let Some(dictionary) = variant.as_var::<GodotDictionary>() {
+            return Self::encode_dictionary_into_with_options(dictionary, writer, options);
+        }
+
+        if let Some(array) = variant.as_var::<GodotArray>() {
+            return Self::encode_array_into_with_options(array, writer, options);
+        }
+
+        if let Some(array) = variant.as_var::<GodotTypedArray>() {
+            return Self::encode_typed_array_into_with_options(array, writer, options);
+        }
+
+        // The encoders for single types always write godot 4 ids, so the header is rewritten
+        if options.protocol == ProtocolVersion::Godot3 {
+            // Godot 3 only sends the header of a RID
+            let mut writer = HeaderWriter::new(writer, id, type_idx == GodotTypeIndex::RID);
+            return Self::encode_plain_variant_into(variant, &mut writer);
+        }
+
+        Self::encode_plain_variant_into(variant, writer)
+    }
+
+    /// Encodes a Godot variant onto the end of a vec, reusing its capacity. If this fails the vec
+    /// is left as it was
+    pub fn encode_into_vec(
+        variant: &dyn GodotVariant,
+        bytes: &mut Vec<u8>,
+    ) -> Result<(), EncodeError> {
+        Self::encode_into_vec_with_options(variant, bytes, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot variant onto the end of a vec using the options given
+    pub fn encode_into_vec_with_options(
+        variant: &dyn GodotVariant,
+        bytes: &mut Vec<u8>,
+        options: &EncodeOptions,
+    ) -> Result<(), EncodeError> {
+        let length = bytes.len();
+
+        Self::encode_into_with_options(variant, bytes, options).inspect_err(|_| {
+            bytes.truncate(length);
+        })
+    }
+
+    /// The amount of bytes the variant encodes to, this can be used to reserve space before
+    /// encoding into a buffer
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut bytes = Vec::with_capacity(4 + Encoder::encoded_len(&world_state)?);
+    ///
+    /// // Prefix the packet with its length
+    /// bytes.extend_from_slice(&(Encoder::encoded_len(&world_state)? as u32).to_le_bytes());
+    /// Encoder::encode_into_vec(&world_state, &mut bytes)?;
+    /// ```
+    pub fn encoded_len(variant: &dyn GodotVariant) -> Result<usize, EncodeError> {
+        Self::encoded_len_with_options(variant, &EncodeOptions::default())
+    }
+
+    /// The amount of bytes the variant encodes to using the options given
+    pub fn encoded_len_with_options(
+        variant: &dyn GodotVariant,
+        options: &EncodeOptions,
+    ) -> Result<usize, EncodeError> {
+        let mut counter = ByteCounter::default();
+        Self::encode_into_with_options(variant, &mut counter, options)?;
+
+        Ok(counter.count)
+    }
+
+    /// The id a type is written with in the header for the protocol version of the options
+    fn type_id(type_idx: GodotTypeIndex, options: &EncodeOptions) -> Result<u16, EncodeError> {
+        type_idx
+            .id(options.protocol)
+            .ok_or(EncodeError::UnsupportedByProtocol {
+                type_idx,
+                protocol: options.protocol,
+            })
+    }
+
+    /// Probes a variant that can't hold other variants for its type and encodes it
+    fn encode_plain_variant_into<W: Write + ?Sized>(
+        variant: &dyn GodotVariant,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        if let Some(nil) = variant.as_var::<GodotNull>() {
+            return Self::encode_nil_into(nil, writer);
+        }
+
         if let Some(bool) = variant.as_var::<GodotBool>() {
-            return Self::encode_bool(bool);
+            return Self::encode_bool_into(bool, writer);
         }
 
         if let Some(integer) = variant.as_var::<GodotInteger>() {
-            return Self::encode_int(integer);
+            return Self::encode_int_into(integer, writer);
         }
 
         if let Some(float) = variant.as_var::<GodotFloat>() {
-            return Self::encode_float(float);
+            return Self::encode_float_into(float, writer);
         }
 
         if let Some(string) = variant.as_var::<GodotString>() {
-            return Self::encode_string(string);
+            return Self::encode_string_into(string, writer);
+        }
+
+        if let Some(string_name) = variant.as_var::<GodotStringName>() {
+            return Self::encode_string_name_into(string_name, writer);
+        }
+
+        if let Some(node_path) = variant.as_var::<GodotNodePath>() {
+            return Self::encode_node_path_into(node_path, writer);
+        }
+
+        if let Some(rid) = variant.as_var::<GodotRid>() {
+            return Self::encode_rid_into(rid, writer);
+        }
+
+        if let Some(callable) = variant.as_var::<GodotCallable>() {
+            return Self::encode_callable_into(callable, writer);
+        }
+
+        if let Some(signal) = variant.as_var::<GodotSignal>() {
+            return Self::encode_signal_into(signal, writer);
         }
 
         if let Some(vector2) = variant.as_var::<GodotVector2>() {
-            return Self::encode_vector2(vector2);
+            return Self::encode_vector2_into(vector2, writer);
         }
 
         if let Some(vector3) = variant.as_var::<GodotVector3>() {
-            return Self::encode_vector3(vector3);
+            return Self::encode_vector3_into(vector3, writer);
         }
 
-        if let Some(dictionary) = variant.as_var::<GodotDictionary>() {
-            return Self::encode_dictionary(dictionary);
+        if let Some(vector4) = variant.as_var::<GodotVector4>() {
+            return Self::encode_vector4_into(vector4, writer);
+        }
+
+        if let Some(vector2i) = variant.as_var::<GodotVector2i>() {
+            return Self::encode_vector2i_into(vector2i, writer);
         }
 
-        Err(anyhow!(
-            "Variant of {:?} is not supported by the encoder",
-            variant
-        ))
+        if let Some(vector3i) = variant.as_var::<GodotVector3i>() {
+            return Self::encode_vector3i_into(vector3i, writer);
+        }
+
+        if let Some(vector4i) = variant.as_var::<GodotVector4i>() {
+            return Self::encode_vector4i_into(vector4i, writer);
+        }
+
+        if let Some(rect2) = variant.as_var::<GodotRect2>() {
+            return Self::encode_rect2_into(rect2, writer);
+        }
+
+        if let Some(rect2i) = variant.as_var::<GodotRect2i>() {
+            return Self::encode_rect2i_into(rect2i, writer);
+        }
+
+        if let Some(aabb) = variant.as_var::<GodotAabb>() {
+            return Self::encode_aabb_into(aabb, writer);
+        }
+
+        if let Some(transform2d) = variant.as_var::<GodotTransform2D>() {
+            return Self::encode_transform2d_into(transform2d, writer);
+        }
+
+        if let Some(basis) = variant.as_var::<GodotBasis>() {
+            return Self::encode_basis_into(basis, writer);
+        }
+
+        if let Some(transform3d) = variant.as_var::<GodotTransform3D>() {
+            return Self::encode_transform3d_into(transform3d, writer);
+        }
+
+        if let Some(projection) = variant.as_var::<GodotProjection>() {
+            return Self::encode_projection_into(projection, writer);
+        }
+
+        if let Some(quaternion) = variant.as_var::<GodotQuaternion>() {
+            return Self::encode_quaternion_into(quaternion, writer);
+        }
+
+        if let Some(plane) = variant.as_var::<GodotPlane>() {
+            return Self::encode_plane_into(plane, writer);
+        }
+
+        if let Some(color) = variant.as_var::<GodotColor>() {
+            return Self::encode_color_into(color, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedByteArray>() {
+            return Self::encode_packed_byte_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedInt32Array>() {
+            return Self::encode_packed_int32_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedInt64Array>() {
+            return Self::encode_packed_int64_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedFloat32Array>() {
+            return Self::encode_packed_float32_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedFloat64Array>() {
+            return Self::encode_packed_float64_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedStringArray>() {
+            return Self::encode_packed_string_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedVector2Array>() {
+            return Self::encode_packed_vector2_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedVector3Array>() {
+            return Self::encode_packed_vector3_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedVector4Array>() {
+            return Self::encode_packed_vector4_array_into(array, writer);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedColorArray>() {
+            return Self::encode_packed_color_array_into(array, writer);
+        }
+
+        Err(EncodeError::Unsupported(variant.type_index()))
+    }
+
+    /// Encodes into a new vec, this is how the encoders that return their bytes are made from the
+    /// encoders that write into a writer
+    fn encode_to_vec(
+        encode: impl FnOnce(&mut Vec<u8>) -> Result<(), EncodeError>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let mut bytes = Vec::new();
+        encode(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Writes the type and flag that every variant starts with, using the godot 4 id of the type
+    fn write_header<W: Write + ?Sized>(
+        writer: &mut W,
+        type_idx: GodotTypeIndex,
+        flag: &SerializeFlag,
+    ) -> Result<(), EncodeError> {
+        writer.write_i16::<LittleEndian>(type_idx as i16)?;
+        writer.write_i16::<LittleEndian>(flag.bits() as i16)?;
+
+        Ok(())
+    }
+
+    /// The flag for a variant made up of real components, godot builds with double precision
+    /// write them as 64 bit floats
+    fn real_flag(double_precision: bool) -> SerializeFlag {
+        if double_precision {
+            SerializeFlag::Bit64
+        } else {
+            SerializeFlag::None
+        }
+    }
+
+    /// Writes real components as 32 bit floats, or 64 bit floats when the flag is
+    /// [Bit64](SerializeFlag::Bit64)
+    fn write_reals<W: Write + ?Sized>(
+        writer: &mut W,
+        components: &[f64],
+        flag: &SerializeFlag,
+    ) -> Result<(), EncodeError> {
+        for component in components {
+            if flag == &SerializeFlag::Bit64 {
+                writer.write_f64::<LittleEndian>(*component)?;
+            } else {
+                writer.write_f32::<LittleEndian>(*component as f32)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the header of a real type followed by each of its components
+    fn encode_reals_into<W: Write + ?Sized>(
+        writer: &mut W,
+        type_idx: GodotTypeIndex,
+        components: &[f64],
+        double_precision: bool,
+    ) -> Result<(), EncodeError> {
+        let flag = Self::real_flag(double_precision);
+
+        Self::write_header(writer, type_idx, &flag)?;
+        Self::write_reals(writer, components, &flag)
     }
 }
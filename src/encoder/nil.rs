@@ -0,0 +1,77 @@
+use std::io::Write;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::EncodeError,
+    types::{primitive::GodotNull, GodotTypeIndex, SerializeFlag},
+};
+
+use super::Encoder;
+
+impl Encoder {
+    /// Encodes a Godot null into bytes, a null is only its header
+    pub fn encode_nil(nil: &GodotNull) -> Result<Vec<u8>, EncodeError> {
+        let mut bytes = Vec::new();
+        Self::encode_nil_into(nil, &mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Encodes a Godot null into a writer
+    pub fn encode_nil_into<W: Write + ?Sized>(
+        _: &GodotNull,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        let mut bytes = [0; 4];
+        LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Nil as i16);
+        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None.bits() as i16);
+
+        Ok(writer.write_all(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        types::{
+            primitive::{GodotInteger, GodotNull},
+            structures::GodotArray,
+            variant::{GodotVariant, Variant},
+        },
+    };
+
+    #[test]
+    fn encode_nil() {
+        let expected_bytes = [0, 0, 0, 0].to_vec();
+        let bytes = Encoder::encode_variant(&GodotNull).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_array_with_null() {
+        let array = GodotArray::new_from_vec(vec![
+            Box::new(GodotNull),
+            Box::new(GodotInteger::new_from_i32(1)),
+        ]);
+        let bytes = Encoder::encode_variant(&array).unwrap();
+        let decoded = Decoder::decode_variant(&bytes).unwrap();
+
+        assert!(
+            array.variant_eq(&*decoded),
+            "Expected {:?} but got {:?}",
+            array,
+            decoded
+        );
+
+        let bytes = Encoder::encode_variant(Variant::Nil.as_dyn()).unwrap();
+        assert_eq!(bytes, [0, 0, 0, 0]);
+    }
+}
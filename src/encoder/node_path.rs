@@ -0,0 +1,67 @@
+use std::io::Write;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{node_path::GodotNodePath, variant::GodotVariant, GodotTypeIndex, SerializeFlag},
+};
+
+use super::Encoder;
+
+/// Set on the name count to mark the node path as the new format
+const NEW_FORMAT_BIT: u32 = 0x80000000;
+/// Set in the node path flags when the path is absolute
+const ABSOLUTE_FLAG: u32 = 1;
+
+impl Encoder {
+    /// Encodes a Godot node path into bytes. Godot writes the name count, the subname count and
+    /// the flags followed by every name and then every subname
+    pub fn encode_node_path(node_path: &GodotNodePath) -> Result<Vec<u8>, EncodeError> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(node_path.byte_length());
+        Self::encode_node_path_into(node_path, &mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Encodes a Godot node path into a writer
+    pub fn encode_node_path_into<W: Write + ?Sized>(
+        node_path: &GodotNodePath,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::NodePath, &SerializeFlag::None)?;
+        writer.write_u32::<LittleEndian>(node_path.names.len() as u32 | NEW_FORMAT_BIT)?;
+        writer.write_u32::<LittleEndian>(node_path.subnames.len() as u32)?;
+
+        let flags = if node_path.absolute { ABSOLUTE_FLAG } else { 0 };
+        writer.write_u32::<LittleEndian>(flags)?;
+
+        for name in node_path.names.iter().chain(node_path.subnames.iter()) {
+            Self::encode_raw_string_into(name, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{encoder::Encoder, types::node_path::GodotNodePath};
+
+    #[test]
+    fn encode_node_path() {
+        let expected_bytes = [
+            22, 0, 0, 0, 1, 0, 0, 128, 1, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 114, 111, 111, 116, 1,
+            0, 0, 0, 120, 0, 0, 0,
+        ]
+        .to_vec();
+        let value: GodotNodePath = "/root:x".parse().unwrap();
+        let bytes = Encoder::encode_node_path(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::io::Write;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{
+        object::{GodotCallable, GodotObject, GodotRid, GodotSignal},
+        GodotTypeIndex, SerializeFlag,
+    },
+};
+
+use super::{EncodeOptions, Encoder};
+
+impl Encoder {
+    /// Encodes a Godot rid into bytes
+    pub fn encode_rid(rid: &GodotRid) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_rid_into(rid, bytes))
+    }
+
+    /// Encodes a Godot rid into a writer
+    pub fn encode_rid_into<W: Write + ?Sized>(
+        rid: &GodotRid,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::RID, &SerializeFlag::None)?;
+        writer.write_u64::<LittleEndian>(rid.id)?;
+
+        Ok(())
+    }
+
+    /// Encodes a Godot callable into bytes. Godot doesn't serialize callables so this is only the
+    /// type header
+    pub fn encode_callable(callable: &GodotCallable) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_callable_into(callable, bytes))
+    }
+
+    /// Encodes a Godot callable into a writer
+    pub fn encode_callable_into<W: Write + ?Sized>(
+        _callable: &GodotCallable,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::Callable, &SerializeFlag::None)
+    }
+
+    /// Encodes a Godot signal into bytes, the signal name followed by the id of its object
+    pub fn encode_signal(signal: &GodotSignal) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_signal_into(signal, bytes))
+    }
+
+    /// Encodes a Godot signal into a writer
+    pub fn encode_signal_into<W: Write + ?Sized>(
+        signal: &GodotSignal,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::Signal, &SerializeFlag::None)?;
+        Self::encode_raw_string_into(&signal.name, writer)?;
+        writer.write_u64::<LittleEndian>(signal.object_id)?;
+
+        Ok(())
+    }
+
+    /// Encodes a Godot object into bytes. An object with an id is written as only its instance id,
+    /// otherwise it is written as its class name followed by each property name and value like
+    /// godot's `var_to_bytes_with_objects`
+    pub fn encode_object(object: &GodotObject) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_object_with_options(object, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot object into bytes using the options given for each property value
+    pub fn encode_object_with_options(
+        object: &GodotObject,
+        options: &EncodeOptions,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_object_into_with_options(object, bytes, options))
+    }
+
+    /// Encodes a Godot object into a writer
+    pub fn encode_object_into<W: Write + ?Sized>(
+        object: &GodotObject,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_object_into_with_options(object, writer, &EncodeOptions::default())
+    }
+
+    /// Encodes a Godot object into a writer using the options given for each property value
+    pub fn encode_object_into_with_options<W: Write + ?Sized>(
+        object: &GodotObject,
+        writer: &mut W,
+        options: &EncodeOptions,
+    ) -> Result<(), EncodeError> {
+        writer.write_u16::<LittleEndian>(Self::type_id(GodotTypeIndex::Object, options)?)?;
+
+        if let Some(id) = object.id {
+            writer.write_i16::<LittleEndian>(SerializeFlag::ObjectAsId.bits() as i16)?;
+            writer.write_u64::<LittleEndian>(id)?;
+
+            return Ok(());
+        }
+
+        writer.write_i16::<LittleEndian>(SerializeFlag::None.bits() as i16)?;
+        Self::encode_raw_string_into(&object.class_name, writer)?;
+
+        // A null object is only an empty class name
+        if object.class_name.is_empty() {
+            return Ok(());
+        }
+
+        writer.write_u32::<LittleEndian>(object.properties.len() as u32)?;
+        for (name, value) in object.properties.iter() {
+            Self::encode_raw_string_into(name, writer)?;
+            Self::encode_into_with_options(&**value, writer, options)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encoder::{EncodeOptions, Encoder},
+        types::{
+            object::{GodotObject, GodotRid, GodotSignal},
+            primitive::GodotInteger,
+            variant::GodotVariant,
+            GodotTypeIndex, ProtocolVersion,
+        },
+    };
+
+    #[test]
+    fn encode_rid() {
+        let expected_bytes = [23, 0, 0, 0, 57, 48, 0, 0, 1, 0, 0, 0].to_vec();
+        let value = GodotRid::new(4294979641);
+        let bytes = Encoder::encode_rid(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_godot3_rid() {
+        // Godot 3 only sends the header of a RID
+        let id = GodotTypeIndex::RID.id(ProtocolVersion::Godot3).unwrap() as u8;
+        let expected_bytes = [id, 0, 0, 0].to_vec();
+        let options = EncodeOptions::with_protocol(ProtocolVersion::Godot3);
+        let value = GodotRid::new(4294979641);
+
+        let mut bytes = Vec::new();
+        Encoder::encode_into_with_options(&value, &mut bytes, &options).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+        assert_eq!(
+            Encoder::encoded_len_with_options(&value, &options).unwrap(),
+            4
+        );
+    }
+
+    #[test]
+    fn encode_signal() {
+        let expected_bytes = [
+            26, 0, 0, 0, 3, 0, 0, 0, 104, 105, 116, 0, 42, 0, 0, 0, 0, 0, 0, 0,
+        ]
+        .to_vec();
+        let value = GodotSignal::new("hit", 42);
+        let bytes = Encoder::encode_signal(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_object() {
+        let expected_bytes = [
+            24, 0, 0, 0, 4, 0, 0, 0, 78, 111, 100, 101, 1, 0, 0, 0, 2, 0, 0, 0, 104, 112, 0, 0, 2,
+            0, 0, 0, 100, 0, 0, 0,
+        ]
+        .to_vec();
+        let mut value = GodotObject::new("Node");
+        value.set("hp", GodotInteger::new_from_i32(100));
+        let bytes = Encoder::encode_object(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+        assert_eq!(value.byte_length(), bytes.len());
+    }
+}
@@ -0,0 +1,388 @@
+use std::io::Write;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{
+        packed::{
+            GodotPackedByteArray, GodotPackedColorArray, GodotPackedFloat32Array,
+            GodotPackedFloat64Array, GodotPackedInt32Array, GodotPackedInt64Array,
+            GodotPackedStringArray, GodotPackedVector2Array, GodotPackedVector3Array,
+            GodotPackedVector4Array,
+        },
+        GodotTypeIndex, SerializeFlag,
+    },
+};
+
+use super::Encoder;
+
+impl Encoder {
+    /// Encodes a Godot packed byte array into bytes. The bytes are padded to a multiple of 4
+    pub fn encode_packed_byte_array(array: &GodotPackedByteArray) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_byte_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed byte array into a writer
+    pub fn encode_packed_byte_array_into<W: Write + ?Sized>(
+        array: &GodotPackedByteArray,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedByteArray,
+            &SerializeFlag::None,
+            array.values.len(),
+        )?;
+
+        let length = array.values.len();
+        // Pad 4 bytes because godot
+        let pad = (4 - (length % 4)) % 4;
+
+        writer.write_all(&array.values)?;
+        writer.write_all(&[0; 3][..pad])?;
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed 32 bit integer array into bytes
+    pub fn encode_packed_int32_array(
+        array: &GodotPackedInt32Array,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_int32_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed 32 bit integer array into a writer
+    pub fn encode_packed_int32_array_into<W: Write + ?Sized>(
+        array: &GodotPackedInt32Array,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedInt32Array,
+            &SerializeFlag::None,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            writer.write_i32::<LittleEndian>(*value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed 64 bit integer array into bytes
+    pub fn encode_packed_int64_array(
+        array: &GodotPackedInt64Array,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_int64_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed 64 bit integer array into a writer
+    pub fn encode_packed_int64_array_into<W: Write + ?Sized>(
+        array: &GodotPackedInt64Array,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedInt64Array,
+            &SerializeFlag::None,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            writer.write_i64::<LittleEndian>(*value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed 32 bit float array into bytes
+    pub fn encode_packed_float32_array(
+        array: &GodotPackedFloat32Array,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_float32_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed 32 bit float array into a writer
+    pub fn encode_packed_float32_array_into<W: Write + ?Sized>(
+        array: &GodotPackedFloat32Array,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedFloat32Array,
+            &SerializeFlag::None,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            writer.write_f32::<LittleEndian>(*value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed 64 bit float array into bytes
+    pub fn encode_packed_float64_array(
+        array: &GodotPackedFloat64Array,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_float64_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed 64 bit float array into a writer
+    pub fn encode_packed_float64_array_into<W: Write + ?Sized>(
+        array: &GodotPackedFloat64Array,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedFloat64Array,
+            &SerializeFlag::None,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            writer.write_f64::<LittleEndian>(*value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed string array into bytes. Each string is written with a null
+    /// terminator and padded to a multiple of 4
+    pub fn encode_packed_string_array(
+        array: &GodotPackedStringArray,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_string_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed string array into a writer
+    pub fn encode_packed_string_array_into<W: Write + ?Sized>(
+        array: &GodotPackedStringArray,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedStringArray,
+            &SerializeFlag::None,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            let length = value.len() + 1;
+            let pad = (4 - (length % 4)) % 4;
+
+            writer.write_i32::<LittleEndian>(length as i32)?;
+            writer.write_all(value.as_bytes())?;
+            // The null terminator followed by the padding
+            writer.write_all(&[0; 4][..1 + pad])?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed Vector2 array into bytes
+    pub fn encode_packed_vector2_array(
+        array: &GodotPackedVector2Array,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_vector2_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed Vector2 array into a writer
+    pub fn encode_packed_vector2_array_into<W: Write + ?Sized>(
+        array: &GodotPackedVector2Array,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        let flag = Self::real_flag(array.double_precision);
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedVector2Array,
+            &flag,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            Self::write_reals(writer, &[value.x, value.y], &flag)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed Vector3 array into bytes
+    pub fn encode_packed_vector3_array(
+        array: &GodotPackedVector3Array,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_vector3_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed Vector3 array into a writer
+    pub fn encode_packed_vector3_array_into<W: Write + ?Sized>(
+        array: &GodotPackedVector3Array,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        let flag = Self::real_flag(array.double_precision);
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedVector3Array,
+            &flag,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            Self::write_reals(writer, &[value.x, value.y, value.z], &flag)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed Vector4 array into bytes
+    pub fn encode_packed_vector4_array(
+        array: &GodotPackedVector4Array,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_vector4_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed Vector4 array into a writer
+    pub fn encode_packed_vector4_array_into<W: Write + ?Sized>(
+        array: &GodotPackedVector4Array,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        let flag = Self::real_flag(array.double_precision);
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedVector4Array,
+            &flag,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            Self::write_reals(writer, &[value.x, value.y, value.z, value.w], &flag)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a Godot packed Color array into bytes
+    pub fn encode_packed_color_array(
+        array: &GodotPackedColorArray,
+    ) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_packed_color_array_into(array, bytes))
+    }
+
+    /// Encodes a Godot packed Color array into a writer
+    pub fn encode_packed_color_array_into<W: Write + ?Sized>(
+        array: &GodotPackedColorArray,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_packed_header(
+            writer,
+            GodotTypeIndex::PackedColorArray,
+            &SerializeFlag::None,
+            array.values.len(),
+        )?;
+
+        for value in array.values.iter() {
+            Self::write_reals(
+                writer,
+                &[
+                    value.r as f64,
+                    value.g as f64,
+                    value.b as f64,
+                    value.a as f64,
+                ],
+                &SerializeFlag::None,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the header and length that every packed array starts with
+    fn write_packed_header<W: Write + ?Sized>(
+        writer: &mut W,
+        type_idx: GodotTypeIndex,
+        flag: &SerializeFlag,
+        length: usize,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, type_idx, flag)?;
+        writer.write_i32::<LittleEndian>(length as i32)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encoder::Encoder,
+        types::{
+            packed::{
+                GodotPackedByteArray, GodotPackedFloat32Array, GodotPackedStringArray,
+                GodotPackedVector2Array,
+            },
+            structures::GodotVector2,
+        },
+    };
+
+    #[test]
+    fn encode_packed_byte_array() {
+        let expected_bytes = [29, 0, 0, 0, 5, 0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 0].to_vec();
+        let value = GodotPackedByteArray::new(vec![1, 2, 3, 4, 5]);
+        let bytes = Encoder::encode_packed_byte_array(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_packed_float32_array() {
+        let expected_bytes = [32, 0, 0, 0, 2, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 192].to_vec();
+        let value = GodotPackedFloat32Array::new(vec![1.0, -2.0]);
+        let bytes = Encoder::encode_packed_float32_array(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_packed_string_array() {
+        let expected_bytes = [
+            34, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 104, 101, 121, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+        ]
+        .to_vec();
+        let value = GodotPackedStringArray::new(vec!["hey".to_owned(), "".to_owned()]);
+        let bytes = Encoder::encode_packed_string_array(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_packed_vector2_array() {
+        let expected_bytes = [
+            35, 0, 0, 0, 2, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 64, 0, 0, 64, 64, 0, 0, 128, 64,
+        ]
+        .to_vec();
+        let value = GodotPackedVector2Array::new(vec![
+            GodotVector2::new(1.0, 2.0),
+            GodotVector2::new(3.0, 4.0),
+        ]);
+        let bytes = Encoder::encode_packed_vector2_array(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+}
@@ -1,39 +1,103 @@
-use byteorder::{ByteOrder, LittleEndian};
+use std::io::Write;
 
-use crate::types::{primitive::GodotString, SerializeFlag, GodotTypeIndex};
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{
+        primitive::{GodotString, GodotStringName},
+        GodotTypeIndex, SerializeFlag,
+    },
+};
 
 use super::Encoder;
 
 impl Encoder {
     /// Encodes a Godot String into bytes
-    pub fn encode_string(string: &GodotString) -> anyhow::Result<Vec<u8>> {
-        Ok(Self::encode_owned_string(string.value.clone()))
+    pub fn encode_string(string: &GodotString) -> Result<Vec<u8>, EncodeError> {
+        Ok(Self::encode_str(&string.value))
+    }
+
+    /// Encodes a Godot String into a writer
+    pub fn encode_string_into<W: Write + ?Sized>(
+        string: &GodotString,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::String, &SerializeFlag::None)?;
+        Self::encode_raw_string_into(&string.value, writer)
+    }
+
+    /// Encodes a Godot StringName into bytes, it is written the same way as a string but with its
+    /// own type
+    pub fn encode_string_name(string_name: &GodotStringName) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_string_name_into(string_name, bytes))
+    }
+
+    /// Encodes a Godot StringName into a writer
+    pub fn encode_string_name_into<W: Write + ?Sized>(
+        string_name: &GodotStringName,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::StringName, &SerializeFlag::None)?;
+        Self::encode_raw_string_into(&string_name.value, writer)
     }
 
     /// Encodes an owned String into bytes
     pub fn encode_owned_string(string: String) -> Vec<u8> {
+        Self::encode_str(&string)
+    }
+
+    /// Encodes a str into bytes without a type header. The string is prefixed with its length and
+    /// padded to a multiple of 4 bytes, this is how strings are written inside of other types
+    pub fn encode_raw_string(string: &str) -> Vec<u8> {
         let length = string.len();
         let pad = (4 - (length % 4)) % 4;
-        let total_length = 8 + length + pad;
+        let total_length = 4 + length + pad;
         let mut bytes = vec![0; total_length];
 
-        LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::String as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None as i16);
-        LittleEndian::write_i32(&mut bytes[4..8], length as i32);
-        bytes[8..8 + length].copy_from_slice(string.as_bytes());
+        LittleEndian::write_i32(&mut bytes[0..4], length as i32);
+        bytes[4..4 + length].copy_from_slice(string.as_bytes());
 
         bytes
     }
 
+    /// Writes a str without a type header into a writer, prefixed with its length and padded to
+    /// a multiple of 4 bytes
+    pub fn encode_raw_string_into<W: Write + ?Sized>(
+        string: &str,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        let length = string.len();
+        let pad = (4 - (length % 4)) % 4;
+
+        writer.write_i32::<LittleEndian>(length as i32)?;
+        writer.write_all(string.as_bytes())?;
+        writer.write_all(&[0; 3][..pad])?;
+
+        Ok(())
+    }
+
     /// Encodes a str into bytes
     pub fn encode_str(string: &str) -> Vec<u8> {
-        Self::encode_owned_string(string.to_owned())
+        let mut bytes = vec![0; 4];
+
+        LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::String as i16);
+        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None.bits() as i16);
+        bytes.append(&mut Self::encode_raw_string(string));
+
+        bytes
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{encoder::Encoder, types::primitive::GodotString};
+    use crate::{
+        encoder::Encoder,
+        types::{
+            primitive::{GodotString, GodotStringName},
+            variant::GodotVariant,
+        },
+    };
 
     #[test]
     fn encode_string() {
@@ -50,4 +114,18 @@ mod tests {
             expected_bytes, bytes
         );
     }
+
+    #[test]
+    fn encode_string_name() {
+        let expected_bytes = [21, 0, 0, 0, 5, 0, 0, 0, 104, 101, 108, 108, 111, 0, 0, 0].to_vec();
+        let value = GodotStringName::new("hello");
+        let bytes = Encoder::encode_string_name(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+        assert_eq!(value.byte_length(), bytes.len());
+    }
 }
@@ -0,0 +1,134 @@
+use std::io::Write;
+
+use crate::{
+    error::EncodeError,
+    types::{
+        transform::{
+            GodotBasis, GodotProjection, GodotQuaternion, GodotTransform2D, GodotTransform3D,
+        },
+        GodotTypeIndex,
+    },
+};
+
+use super::Encoder;
+
+impl Encoder {
+    /// Encodes a Transform2D into bytes, the x axis, y axis and origin columns are written in order
+    pub fn encode_transform2d(transform: &GodotTransform2D) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_transform2d_into(transform, bytes))
+    }
+
+    /// Encodes a Transform2D into a writer
+    pub fn encode_transform2d_into<W: Write + ?Sized>(
+        transform: &GodotTransform2D,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Transform2D,
+            &transform.components(),
+            transform.double_precision,
+        )
+    }
+
+    /// Encodes a Basis into bytes, the rows are written in order
+    pub fn encode_basis(basis: &GodotBasis) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_basis_into(basis, bytes))
+    }
+
+    /// Encodes a Basis into a writer
+    pub fn encode_basis_into<W: Write + ?Sized>(
+        basis: &GodotBasis,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Basis,
+            &basis.components(),
+            basis.double_precision,
+        )
+    }
+
+    /// Encodes a Transform3D into bytes, the basis rows are written followed by the origin
+    pub fn encode_transform3d(transform: &GodotTransform3D) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_transform3d_into(transform, bytes))
+    }
+
+    /// Encodes a Transform3D into a writer
+    pub fn encode_transform3d_into<W: Write + ?Sized>(
+        transform: &GodotTransform3D,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Transform3D,
+            &transform.components(),
+            transform.double_precision,
+        )
+    }
+
+    /// Encodes a Projection into bytes, the columns are written in order
+    pub fn encode_projection(projection: &GodotProjection) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_projection_into(projection, bytes))
+    }
+
+    /// Encodes a Projection into a writer
+    pub fn encode_projection_into<W: Write + ?Sized>(
+        projection: &GodotProjection,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Projection,
+            &projection.components(),
+            projection.double_precision,
+        )
+    }
+
+    /// Encodes a Quaternion into bytes
+    pub fn encode_quaternion(quaternion: &GodotQuaternion) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_quaternion_into(quaternion, bytes))
+    }
+
+    /// Encodes a Quaternion into a writer
+    pub fn encode_quaternion_into<W: Write + ?Sized>(
+        quaternion: &GodotQuaternion,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Quaternion,
+            &[quaternion.x, quaternion.y, quaternion.z, quaternion.w],
+            quaternion.double_precision,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encoder::Encoder,
+        types::{structures::GodotVector2, transform::GodotTransform2D},
+    };
+
+    #[test]
+    fn encode_transform2d() {
+        let expected_bytes = [
+            11, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 128, 63, 0, 0, 32, 65, 0, 0,
+            160, 192,
+        ]
+        .to_vec();
+        let value = GodotTransform2D::new(
+            GodotVector2::new(1.0, 0.0),
+            GodotVector2::new(0.0, 1.0),
+            GodotVector2::new(10.0, -5.0),
+        );
+        let bytes = Encoder::encode_transform2d(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+}
@@ -1,42 +1,137 @@
-use byteorder::{ByteOrder, LittleEndian};
+use std::io::Write;
 
-use crate::types::{
-    structures::{GodotVector2, GodotVector3},
-    SerializeFlag, GodotTypeIndex,
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    error::EncodeError,
+    types::{
+        structures::{
+            GodotVector2, GodotVector2i, GodotVector3, GodotVector3i, GodotVector4, GodotVector4i,
+        },
+        GodotTypeIndex, SerializeFlag,
+    },
 };
 
 use super::Encoder;
 
 impl Encoder {
     /// Encodes a Vector2 into bytes
-    pub fn encode_vector2(vec2: &GodotVector2) -> anyhow::Result<Vec<u8>> {
-        let bytes = &mut [0; 12];
-        LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Vector2 as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None as i16);
-        LittleEndian::write_f32(&mut bytes[4..8], vec2.x);
-        LittleEndian::write_f32(&mut bytes[8..12], vec2.y);
+    pub fn encode_vector2(vec2: &GodotVector2) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_vector2_into(vec2, bytes))
+    }
 
-        Ok(bytes.to_vec())
+    /// Encodes a Vector2 into a writer
+    pub fn encode_vector2_into<W: Write + ?Sized>(
+        vec2: &GodotVector2,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Vector2,
+            &[vec2.x, vec2.y],
+            vec2.double_precision,
+        )
     }
 
     /// Encodes a Vector3 into bytes
-    pub fn encode_vector3(vec3: &GodotVector3) -> anyhow::Result<Vec<u8>> {
-        let bytes = &mut [0; 16];
-        LittleEndian::write_i16(&mut bytes[0..2], GodotTypeIndex::Vector3 as i16);
-        LittleEndian::write_i16(&mut bytes[2..4], SerializeFlag::None as i16);
-        LittleEndian::write_f32(&mut bytes[4..8], vec3.x);
-        LittleEndian::write_f32(&mut bytes[8..12], vec3.y);
-        LittleEndian::write_f32(&mut bytes[12..16], vec3.z);
+    pub fn encode_vector3(vec3: &GodotVector3) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_vector3_into(vec3, bytes))
+    }
+
+    /// Encodes a Vector3 into a writer
+    pub fn encode_vector3_into<W: Write + ?Sized>(
+        vec3: &GodotVector3,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Vector3,
+            &[vec3.x, vec3.y, vec3.z],
+            vec3.double_precision,
+        )
+    }
+
+    /// Encodes a Vector4 into bytes
+    pub fn encode_vector4(vec4: &GodotVector4) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_vector4_into(vec4, bytes))
+    }
+
+    /// Encodes a Vector4 into a writer
+    pub fn encode_vector4_into<W: Write + ?Sized>(
+        vec4: &GodotVector4,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::encode_reals_into(
+            writer,
+            GodotTypeIndex::Vector4,
+            &[vec4.x, vec4.y, vec4.z, vec4.w],
+            vec4.double_precision,
+        )
+    }
+
+    /// Encodes a Vector2i into bytes
+    pub fn encode_vector2i(vec2: &GodotVector2i) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_vector2i_into(vec2, bytes))
+    }
+
+    /// Encodes a Vector2i into a writer
+    pub fn encode_vector2i_into<W: Write + ?Sized>(
+        vec2: &GodotVector2i,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::Vector2I, &SerializeFlag::None)?;
+        writer.write_i32::<LittleEndian>(vec2.x)?;
+        writer.write_i32::<LittleEndian>(vec2.y)?;
+
+        Ok(())
+    }
 
-        Ok(bytes.to_vec())
+    /// Encodes a Vector3i into bytes
+    pub fn encode_vector3i(vec3: &GodotVector3i) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_vector3i_into(vec3, bytes))
+    }
+
+    /// Encodes a Vector3i into a writer
+    pub fn encode_vector3i_into<W: Write + ?Sized>(
+        vec3: &GodotVector3i,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::Vector3I, &SerializeFlag::None)?;
+        writer.write_i32::<LittleEndian>(vec3.x)?;
+        writer.write_i32::<LittleEndian>(vec3.y)?;
+        writer.write_i32::<LittleEndian>(vec3.z)?;
+
+        Ok(())
+    }
+
+    /// Encodes a Vector4i into bytes
+    pub fn encode_vector4i(vec4: &GodotVector4i) -> Result<Vec<u8>, EncodeError> {
+        Self::encode_to_vec(|bytes| Self::encode_vector4i_into(vec4, bytes))
+    }
+
+    /// Encodes a Vector4i into a writer
+    pub fn encode_vector4i_into<W: Write + ?Sized>(
+        vec4: &GodotVector4i,
+        writer: &mut W,
+    ) -> Result<(), EncodeError> {
+        Self::write_header(writer, GodotTypeIndex::Vector4I, &SerializeFlag::None)?;
+        writer.write_i32::<LittleEndian>(vec4.x)?;
+        writer.write_i32::<LittleEndian>(vec4.y)?;
+        writer.write_i32::<LittleEndian>(vec4.z)?;
+        writer.write_i32::<LittleEndian>(vec4.w)?;
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        encoder::Encoder,
-        types::structures::{GodotVector2, GodotVector3},
+        encoder::{EncodeOptions, Encoder},
+        types::{
+            structures::{GodotVector2, GodotVector2i, GodotVector3},
+            ProtocolVersion,
+        },
     };
 
     #[test]
@@ -55,7 +150,7 @@ mod tests {
     #[test]
     fn encode_vector3() {
         let expected_bytes =
-            [7, 0, 0, 0, 0, 0, 80, 66, 128, 162, 133, 71, 224, 46, 14, 73].to_vec();
+            [9, 0, 0, 0, 0, 0, 80, 66, 128, 162, 133, 71, 224, 46, 14, 73].to_vec();
         let value = GodotVector3::new(52.0, 68421.0, 582382.0);
         let bytes = Encoder::encode_vector3(&value).unwrap();
 
@@ -65,4 +160,47 @@ mod tests {
             expected_bytes, bytes
         );
     }
+
+    #[test]
+    fn encode_godot3_vector3() {
+        let expected_bytes =
+            [7, 0, 0, 0, 0, 0, 80, 66, 128, 162, 133, 71, 224, 46, 14, 73].to_vec();
+        let value = GodotVector3::new(52.0, 68421.0, 582382.0);
+        let options = EncodeOptions::with_protocol(ProtocolVersion::Godot3);
+        let bytes = Encoder::encode_variant_with_options(&value, &options).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_vector2_double() {
+        let mut expected_bytes = vec![5, 0, 1, 0];
+        expected_bytes.extend_from_slice(&0.1_f64.to_le_bytes());
+        expected_bytes.extend_from_slice(&1e300_f64.to_le_bytes());
+        let value = GodotVector2::new(0.1, 1e300).with_double_precision();
+        let bytes = Encoder::encode_vector2(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
+
+    #[test]
+    fn encode_vector2i() {
+        let expected_bytes = [6, 0, 0, 0, 52, 0, 0, 0, 252, 255, 255, 255].to_vec();
+        let value = GodotVector2i::new(52, -4);
+        let bytes = Encoder::encode_vector2i(&value).unwrap();
+
+        assert_eq!(
+            expected_bytes, bytes,
+            "Expected {:?} but got {:?}",
+            expected_bytes, bytes
+        );
+    }
 }
@@ -0,0 +1,76 @@
+use std::io::{self, Write};
+
+use crate::types::TYPE_PADDING;
+
+/// Counts the bytes written to it without keeping them, used to find how long a variant is when
+/// encoded
+#[derive(Debug, Default)]
+pub(crate) struct ByteCounter {
+    pub(crate) count: usize,
+}
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.count += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Replaces the type id at the start of a variant as it is written. The encoders for single types
+/// always write godot 4 ids, this lets them be written for godot 3 without collecting the bytes
+/// first
+pub(crate) struct HeaderWriter<'a, W: ?Sized> {
+    writer: &'a mut W,
+    id: [u8; 2],
+    header_only: bool,
+    written: usize,
+}
+
+impl<'a, W: Write + ?Sized> HeaderWriter<'a, W> {
+    /// Creates a writer that replaces the type id with the id given. When it is header only
+    /// everything after the type and flag is dropped
+    pub(crate) fn new(writer: &'a mut W, id: u16, header_only: bool) -> Self {
+        Self {
+            writer,
+            id: id.to_le_bytes(),
+            header_only,
+            written: 0,
+        }
+    }
+}
+
+impl<W: Write + ?Sized> Write for HeaderWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written < self.id.len() {
+            let length = buf.len().min(self.id.len() - self.written);
+            self.writer
+                .write_all(&self.id[self.written..self.written + length])?;
+            self.written += length;
+
+            return Ok(length);
+        }
+
+        if self.header_only {
+            let length = buf
+                .len()
+                .min((TYPE_PADDING as usize).saturating_sub(self.written));
+            self.writer.write_all(&buf[..length])?;
+            self.written += buf.len();
+
+            return Ok(buf.len());
+        }
+
+        let length = self.writer.write(buf)?;
+        self.written += length;
+
+        Ok(length)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,246 @@
+use std::string::FromUtf8Error;
+
+use thiserror::Error;
+
+use crate::types::{
+    variant::GodotVariant, ContainerType, GodotTypeIndex, ProtocolVersion, SerializeFlag,
+};
+
+/// An error from decoding bytes. The offset is where in the bytes the decoder failed, counted from
+/// the start of the outermost variant. Why it failed is the [source](std::error::Error::source) of
+/// the error rather than part of its message, so that error reporters don't print it twice
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "failed to decode {} at byte {offset}",
+    .type_idx.map_or("variant".to_owned(), |type_idx| format!("{type_idx:?}"))
+)]
+pub struct DecodeError {
+    /// The position in the bytes of the value that could not be decoded
+    pub offset: usize,
+    /// The type that was being decoded, this is the innermost type when the error happened inside
+    /// of a container. This is None if the type is not known, such as when the type id itself
+    /// could not be read
+    pub type_idx: Option<GodotTypeIndex>,
+    /// Why the bytes could not be decoded
+    #[source]
+    pub kind: DecodeErrorKind,
+}
+
+impl DecodeError {
+    /// Creates an error at an offset in the bytes without a type
+    pub fn new(kind: DecodeErrorKind, offset: usize) -> Self {
+        Self {
+            offset,
+            type_idx: None,
+            kind,
+        }
+    }
+
+    /// Creates an error at the start of the bytes of a type
+    pub(crate) fn at_type(kind: DecodeErrorKind, type_idx: GodotTypeIndex) -> Self {
+        Self {
+            offset: 0,
+            type_idx: Some(type_idx),
+            kind,
+        }
+    }
+
+    /// Sets the type being decoded, unless a value nested inside of it already set its own type
+    pub(crate) fn in_type(mut self, type_idx: GodotTypeIndex) -> Self {
+        self.type_idx.get_or_insert(type_idx);
+        self
+    }
+
+    /// Moves the offset forward, used by containers so that the offset of an error in one of their
+    /// values is counted from the start of the container
+    pub(crate) fn offset_by(mut self, offset: usize) -> Self {
+        self.offset += offset;
+        if let DecodeErrorKind::NotEnoughBytes { needed } = &mut self.kind {
+            *needed = needed.saturating_add(offset);
+        }
+        self
+    }
+}
+
+/// The reason bytes could not be decoded
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DecodeErrorKind {
+    /// The bytes ended before the value did, this usually means the packet was truncated. Needed
+    /// is how many bytes the value needs at least, counted from the same place as the offset, so a
+    /// reader can wait for that many bytes before decoding again
+    #[error("not enough bytes, at least {needed} are needed")]
+    NotEnoughBytes { needed: usize },
+    /// The type id is not a type in the protocol version
+    #[error("unknown type id {0}")]
+    UnknownType(u32),
+    /// The flags in the header can't be used with the type
+    #[error("invalid flag {0:?}")]
+    InvalidFlag(SerializeFlag),
+    /// A string was not valid utf-8
+    #[error("invalid utf-8")]
+    InvalidUtf8(#[source] FromUtf8Error),
+    /// A length is too large to be held in memory
+    #[error("length {0} is too large")]
+    LengthTooLarge(usize),
+    /// Containers were nested inside of each other deeper than the limit allows
+    #[error("containers are nested too deeply")]
+    TooDeep,
+    /// A container holds more values than the limit allows
+    #[error("container length {length} is over the limit of {max}")]
+    ContainerTooLong { length: usize, max: usize },
+    /// A string holds more bytes than the limit allows
+    #[error("string length {length} is over the limit of {max}")]
+    StringTooLong { length: usize, max: usize },
+    /// Decoding the bytes would take up more memory than the limit allows
+    #[error("decoding would allocate more than {max} bytes")]
+    AllocationTooLarge { max: usize },
+    /// The node path was written in the format used before godot 3.0
+    #[error("the old node path format is not supported")]
+    OldNodePathFormat,
+    /// An object was sent with its class name and properties but the options don't allow objects
+    #[error(
+        "decoding full objects is not allowed, use DecodeOptions::with_objects for trusted sources"
+    )]
+    ObjectsNotAllowed,
+    /// A value in a typed container doesn't match the container type
+    #[error(transparent)]
+    TypeMismatch(#[from] TypeMismatchError),
+}
+
+/// An error from decoding variants out of a reader
+#[derive(Debug, Error)]
+pub enum ReadError {
+    /// The bytes that were read could not be decoded. The offset is counted from the start of the
+    /// stream
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+    /// The bytes could not be read
+    #[error("failed to read bytes")]
+    Io(#[from] std::io::Error),
+}
+
+/// An error from encoding a variant
+#[derive(Debug, Error)]
+pub enum EncodeError {
+    /// The encoder doesn't know how to encode the variant
+    #[error("{0:?} is not supported by the encoder")]
+    Unsupported(GodotTypeIndex),
+    /// The type doesn't exist in the protocol version being encoded for
+    #[error("{type_idx:?} is not supported by {protocol:?}")]
+    UnsupportedByProtocol {
+        type_idx: GodotTypeIndex,
+        protocol: ProtocolVersion,
+    },
+    /// A value in a typed container doesn't match the container type
+    #[error(transparent)]
+    TypeMismatch(#[from] TypeMismatchError),
+    /// A rust value could not be turned into a variant
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+    /// The bytes could not be written
+    #[error("failed to write bytes")]
+    Io(#[from] std::io::Error),
+}
+
+/// An error from turning a rust value into a variant or a variant into a rust value with serde
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum SerdeError {
+    /// An error from the serialize or deserialize impl of the rust type, this is also used when
+    /// a variant has a different type than the rust value expects
+    #[error("{0}")]
+    Message(String),
+    /// The variant has no rust equivalent, such as a callable
+    #[error("{0:?} can't be deserialized into a rust value")]
+    Unsupported(GodotTypeIndex),
+    /// The variant made from the rust value could not be encoded
+    #[error(transparent)]
+    Encode(#[from] EncodeError),
+    /// The bytes could not be decoded into a variant
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for SerdeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        SerdeError::Message(msg.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for SerdeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        SerdeError::Message(msg.to_string())
+    }
+}
+
+/// A rust value that could not be converted to or from a variant
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConvertError {
+    /// The variant is not a type the rust value can be read from
+    #[error("expected {expected} but got {found:?}")]
+    UnexpectedType {
+        /// A description of the types that were expected, such as "an integer"
+        expected: &'static str,
+        found: GodotTypeIndex,
+    },
+    /// An integer doesn't fit in the rust type or in the width it is sent with
+    #[error("{value} does not fit in {target}")]
+    OutOfRange { value: i64, target: &'static str },
+    /// An unsigned integer is larger than the largest godot integer, [i64::MAX]
+    #[error("{0} does not fit in a godot integer")]
+    TooLarge(u64),
+    /// A field is missing from the dictionary and has no default
+    #[error("missing field {0}")]
+    MissingField(String),
+    /// An array has fewer values than the fields it is read into
+    #[error("expected {expected} values but got {found}")]
+    InvalidLength { expected: usize, found: usize },
+    /// A field of a struct could not be converted
+    #[error("invalid field {name}: {source}")]
+    Field {
+        name: String,
+        #[source]
+        source: Box<ConvertError>,
+    },
+}
+
+impl ConvertError {
+    /// Creates an error for a variant that is not one of the expected types
+    pub fn unexpected(expected: &'static str, found: &dyn GodotVariant) -> Self {
+        Self::UnexpectedType {
+            expected,
+            found: found.type_index(),
+        }
+    }
+
+    /// Marks the error as coming from a field of a struct
+    pub fn in_field(self, name: &str) -> Self {
+        Self::Field {
+            name: name.to_owned(),
+            source: Box::new(self),
+        }
+    }
+}
+
+/// A value that doesn't match the type of the typed container it is being put in
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("value of type {found:?} does not match the container type {expected:?}")]
+pub struct TypeMismatchError {
+    /// The type of the container
+    pub expected: ContainerType,
+    /// The type of the value
+    pub found: GodotTypeIndex,
+}
+
+/// A boxed variant that is not one of the godot types from this crate, so it has no case in
+/// [Variant](crate::types::variant::Variant). The variant is given back
+#[derive(Debug, Error)]
+#[error("{:?} is not a godot type known to the variant enum", .0.type_index())]
+pub struct UnknownVariantError(pub Box<dyn GodotVariant>);
+
+/// A string that is not a valid HTML color code
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid html color {0}")]
+pub struct ParseColorError(pub String);
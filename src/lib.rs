@@ -1,9 +1,15 @@
 pub mod decoder;
 pub mod encoder;
+pub mod error;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod types;
 
 pub mod prelude {
     pub use crate::decoder;
     pub use crate::encoder;
+    pub use crate::error;
+    #[cfg(feature = "serde")]
+    pub use crate::serde;
     pub use crate::types;
 }
@@ -0,0 +1,461 @@
+use ::serde::{
+    de::{
+        self, value::SeqDeserializer, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+        SeqAccess, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer,
+};
+
+use crate::{
+    error::SerdeError,
+    types::{
+        geometry::{GodotAabb, GodotPlane, GodotRect2, GodotRect2i},
+        node_path::GodotNodePath,
+        object::GodotRid,
+        packed::{
+            GodotPackedByteArray, GodotPackedColorArray, GodotPackedFloat32Array,
+            GodotPackedFloat64Array, GodotPackedInt32Array, GodotPackedInt64Array,
+            GodotPackedStringArray, GodotPackedVector2Array, GodotPackedVector3Array,
+            GodotPackedVector4Array,
+        },
+        primitive::{GodotBool, GodotFloat, GodotInteger, GodotNull, GodotString, GodotStringName},
+        structures::{
+            GodotArray, GodotColor, GodotDictionary, GodotTypedArray, GodotVector2, GodotVector2i,
+            GodotVector3, GodotVector3i, GodotVector4, GodotVector4i,
+        },
+        transform::{
+            GodotBasis, GodotProjection, GodotQuaternion, GodotTransform2D, GodotTransform3D,
+        },
+        variant::{AsVariant, GodotVariant},
+    },
+};
+
+/// Visits the components of the first type the variant matches as a sequence of floats
+macro_rules! visit_components {
+    ($variant:expr, $visitor:expr, $($godot_type:ty),* $(,)?) => {
+        $(
+            if let Some(value) = $variant.as_var::<$godot_type>() {
+                return visit_values(value.components(), $visitor);
+            }
+        )*
+    };
+}
+
+/// Reads a rust value out of a variant. Vectors, colors and transforms are read as a sequence of
+/// their components
+#[derive(Debug, Clone, Copy)]
+pub struct VariantDeserializer<'de> {
+    variant: &'de dyn GodotVariant,
+}
+
+impl<'de> VariantDeserializer<'de> {
+    pub fn new(variant: &'de dyn GodotVariant) -> Self {
+        Self { variant }
+    }
+}
+
+impl<'de> Deserializer<'de> for VariantDeserializer<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let variant = self.variant;
+
+        if variant.as_var::<GodotNull>().is_some() {
+            return visitor.visit_unit();
+        }
+
+        if let Some(bool) = variant.as_var::<GodotBool>() {
+            return visitor.visit_bool(bool.value);
+        }
+
+        if let Some(integer) = variant.as_var::<GodotInteger>() {
+            return visitor.visit_i64(integer.value);
+        }
+
+        if let Some(float) = variant.as_var::<GodotFloat>() {
+            return visitor.visit_f64(float.value);
+        }
+
+        if let Some(string) = variant.as_var::<GodotString>() {
+            return visitor.visit_borrowed_str(&string.value);
+        }
+
+        if let Some(string_name) = variant.as_var::<GodotStringName>() {
+            return visitor.visit_borrowed_str(&string_name.value);
+        }
+
+        if let Some(node_path) = variant.as_var::<GodotNodePath>() {
+            return visitor.visit_string(node_path.to_string());
+        }
+
+        if let Some(rid) = variant.as_var::<GodotRid>() {
+            return visitor.visit_u64(rid.id);
+        }
+
+        visit_components!(
+            variant,
+            visitor,
+            GodotVector2,
+            GodotVector3,
+            GodotVector4,
+            GodotQuaternion,
+            GodotRect2,
+            GodotAabb,
+            GodotPlane,
+            GodotColor,
+            GodotTransform2D,
+            GodotBasis,
+            GodotTransform3D,
+            GodotProjection,
+        );
+
+        if let Some(vector2i) = variant.as_var::<GodotVector2i>() {
+            return visit_values([vector2i.x, vector2i.y], visitor);
+        }
+
+        if let Some(vector3i) = variant.as_var::<GodotVector3i>() {
+            return visit_values([vector3i.x, vector3i.y, vector3i.z], visitor);
+        }
+
+        if let Some(vector4i) = variant.as_var::<GodotVector4i>() {
+            return visit_values([vector4i.x, vector4i.y, vector4i.z, vector4i.w], visitor);
+        }
+
+        if let Some(rect2i) = variant.as_var::<GodotRect2i>() {
+            let GodotRect2i { position, size } = rect2i;
+            return visit_values([position.x, position.y, size.x, size.y], visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedByteArray>() {
+            return visit_values(array.values.iter().copied(), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedInt32Array>() {
+            return visit_values(array.values.iter().copied(), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedInt64Array>() {
+            return visit_values(array.values.iter().copied(), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedFloat32Array>() {
+            return visit_values(array.values.iter().copied(), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedFloat64Array>() {
+            return visit_values(array.values.iter().copied(), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedStringArray>() {
+            return visit_values(array.values.iter().map(String::as_str), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedVector2Array>() {
+            return visit_variants(array.values.iter().map(|v| v as &dyn GodotVariant), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedVector3Array>() {
+            return visit_variants(array.values.iter().map(|v| v as &dyn GodotVariant), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedVector4Array>() {
+            return visit_variants(array.values.iter().map(|v| v as &dyn GodotVariant), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotPackedColorArray>() {
+            return visit_variants(array.values.iter().map(|v| v as &dyn GodotVariant), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotArray>() {
+            return visit_variants(array.values.iter().map(|v| &**v), visitor);
+        }
+
+        if let Some(array) = variant.as_var::<GodotTypedArray>() {
+            return visit_variants(array.values.iter().map(|v| &**v), visitor);
+        }
+
+        if let Some(dictionary) = variant.as_var::<GodotDictionary>() {
+            let mut map = VariantMap {
+                entries: dictionary.map.iter(),
+                value: None,
+            };
+            let value = visitor.visit_map(&mut map)?;
+
+            return match map.entries.len() {
+                0 => Ok(value),
+                _ => Err(de::Error::invalid_length(
+                    dictionary.map.len(),
+                    &"fewer entries in the dictionary",
+                )),
+            };
+        }
+
+        Err(SerdeError::Unsupported(variant.type_index()))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.variant.as_var::<GodotNull>().is_some() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.variant.as_var::<GodotPackedByteArray>() {
+            Some(array) => visitor.visit_borrowed_bytes(&array.values),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        // Enum variants sent by their index are identified with an unsigned integer
+        match self.variant.as_var::<GodotInteger>() {
+            Some(integer) if integer.value >= 0 => visitor.visit_u64(integer.value as u64),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let variant = self.variant;
+
+        // A unit variant sent by its name or index
+        if variant.as_var::<GodotString>().is_some()
+            || variant.as_var::<GodotStringName>().is_some()
+            || variant.as_var::<GodotInteger>().is_some()
+        {
+            return visitor.visit_enum(VariantEnum {
+                identifier: variant,
+                fields: VariantFields::None,
+            });
+        }
+
+        // A variant with fields sent as a dictionary of its name to its fields
+        if let Some(dictionary) = variant.as_var::<GodotDictionary>() {
+            if let (1, Some((name, fields))) = (dictionary.map.len(), dictionary.map.first()) {
+                return visitor.visit_enum(VariantEnum {
+                    identifier: &**name,
+                    fields: VariantFields::Variant(&**fields),
+                });
+            }
+        }
+
+        // A variant with fields sent as an array of its index followed by its fields
+        if let Some(array) = variant.as_var::<GodotArray>() {
+            if let [index, fields @ ..] = array.values.as_slice() {
+                return visitor.visit_enum(VariantEnum {
+                    identifier: &**index,
+                    fields: VariantFields::Values(fields),
+                });
+            }
+        }
+
+        Err(de::Error::custom(format!(
+            "expected an enum but got {:?}",
+            variant.type_index()
+        )))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string unit unit_struct seq
+        tuple tuple_struct map struct ignored_any
+    }
+}
+
+/// Visits values that can be turned into a deserializer as a sequence
+fn visit_values<'de, I, V>(values: I, visitor: V) -> Result<V::Value, SerdeError>
+where
+    I: IntoIterator,
+    I::Item: IntoDeserializer<'de, SerdeError>,
+    V: Visitor<'de>,
+{
+    SeqDeserializer::new(values.into_iter()).deserialize_any(visitor)
+}
+
+/// Visits variants as a sequence, every variant has to be read
+fn visit_variants<'de, I, V>(values: I, visitor: V) -> Result<V::Value, SerdeError>
+where
+    I: ExactSizeIterator<Item = &'de dyn GodotVariant>,
+    V: Visitor<'de>,
+{
+    let length = values.len();
+    let mut seq = VariantSeq { values };
+    let value = visitor.visit_seq(&mut seq)?;
+
+    match seq.values.len() {
+        0 => Ok(value),
+        _ => Err(de::Error::invalid_length(
+            length,
+            &"fewer values in the array",
+        )),
+    }
+}
+
+/// The values of an array
+struct VariantSeq<I> {
+    values: I,
+}
+
+impl<'de, I> SeqAccess<'de> for VariantSeq<I>
+where
+    I: ExactSizeIterator<Item = &'de dyn GodotVariant>,
+{
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.values
+            .next()
+            .map(|value| seed.deserialize(VariantDeserializer::new(value)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// The entries of a dictionary
+struct VariantMap<'de> {
+    entries: indexmap::map::Iter<'de, Box<dyn GodotVariant>, Box<dyn GodotVariant>>,
+    /// The value of the key that was read last
+    value: Option<&'de dyn GodotVariant>,
+}
+
+impl<'de> MapAccess<'de> for VariantMap<'de> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(&**value);
+
+        seed.deserialize(VariantDeserializer::new(&**key)).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self.value.take().ok_or_else(|| {
+            <SerdeError as de::Error>::custom("a dictionary value was read before its key")
+        })?;
+
+        seed.deserialize(VariantDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// An enum variant, identified by its name or index, and its fields
+struct VariantEnum<'de> {
+    identifier: &'de dyn GodotVariant,
+    fields: VariantFields<'de>,
+}
+
+/// The fields of an enum variant
+enum VariantFields<'de> {
+    /// A unit variant sent as only its name or index
+    None,
+    /// The fields in one variant, the value of a variant sent as a dictionary of its name
+    Variant(&'de dyn GodotVariant),
+    /// The values after the index of a variant sent as an array
+    Values(&'de [Box<dyn GodotVariant>]),
+}
+
+impl<'de> VariantEnum<'de> {
+    /// The fields of a variant that holds them in a single value, such as a newtype or struct
+    /// variant
+    fn fields(&self) -> Result<VariantDeserializer<'de>, SerdeError> {
+        match self.fields {
+            VariantFields::Variant(fields) => Ok(VariantDeserializer::new(fields)),
+            VariantFields::Values([fields]) => Ok(VariantDeserializer::new(&**fields)),
+            VariantFields::Values(values) => Err(de::Error::invalid_length(
+                values.len(),
+                &"one value after the index",
+            )),
+            VariantFields::None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"enum fields",
+            )),
+        }
+    }
+}
+
+impl<'de> EnumAccess<'de> for VariantEnum<'de> {
+    type Error = SerdeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(VariantDeserializer::new(self.identifier))?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantEnum<'de> {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.fields {
+            VariantFields::Variant(fields) => <()>::deserialize(VariantDeserializer::new(fields)),
+            VariantFields::Values([]) | VariantFields::None => Ok(()),
+            VariantFields::Values(values) => Err(de::Error::invalid_length(
+                values.len(),
+                &"no values after the index",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.fields()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.fields {
+            VariantFields::Values(values) => {
+                visit_variants(values.iter().map(|value| &**value), visitor)
+            }
+            _ => self.fields()?.deserialize_seq(visitor),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.fields()?.deserialize_map(visitor)
+    }
+}
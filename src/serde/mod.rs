@@ -0,0 +1,116 @@
+//! Maps rust types to godot variants with serde, so messages can be defined as rust structs
+//! instead of building dictionaries by hand. Structs and maps are dictionaries with string keys,
+//! sequences and tuples are arrays and `None` and `()` are null
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct PlayerState {
+//!     hp: i32,
+//!     name: String,
+//!     position: (f32, f32),
+//! }
+//!
+//! let bytes = serde::to_bytes(&state)?;
+//! let state: PlayerState = serde::from_bytes(&bytes)?;
+//! ```
+
+use ::serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    decoder::{DecodeOptions, Decoder},
+    encoder::{EncodeOptions, Encoder},
+    error::SerdeError,
+    types::variant::GodotVariant,
+};
+
+pub mod de;
+pub mod ser;
+
+/// How rust enums are turned into variants. Enums can be read back from either representation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnumRepresentation {
+    /// Unit variants are their name as a string. Other variants are a dictionary with the name
+    /// as the only key and the fields as the value, this is how serde represents enums by default
+    #[default]
+    Name,
+    /// Unit variants are their index as an integer, this matches how godot sends enums. Other
+    /// variants are an array of the index followed by the fields, so a tuple variant is
+    /// `[index, a, b]` and can be matched on in godot. A newtype variant is `[index, value]` and a
+    /// struct variant is `[index, { "field": value }]` with its fields in a dictionary
+    Index,
+}
+
+/// Changes how rust values are turned into variants and encoded
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+    /// How enums are represented
+    pub enums: EnumRepresentation,
+    /// The options the variant is encoded with
+    pub encode: EncodeOptions,
+}
+
+impl SerializeOptions {
+    /// The default options but with a different representation for enums
+    pub fn with_enums(enums: EnumRepresentation) -> Self {
+        Self {
+            enums,
+            ..Default::default()
+        }
+    }
+}
+
+/// Turns a rust value into a variant and encodes it into bytes
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, SerdeError> {
+    to_bytes_with_options(value, &SerializeOptions::default())
+}
+
+/// Turns a rust value into a variant and encodes it into bytes using the options given
+pub fn to_bytes_with_options<T: Serialize + ?Sized>(
+    value: &T,
+    options: &SerializeOptions,
+) -> Result<Vec<u8>, SerdeError> {
+    let variant = to_variant_with_options(value, options)?;
+
+    Ok(Encoder::encode_variant_with_options(
+        &*variant,
+        &options.encode,
+    )?)
+}
+
+/// Turns a rust value into a variant without encoding it
+pub fn to_variant<T: Serialize + ?Sized>(value: &T) -> Result<Box<dyn GodotVariant>, SerdeError> {
+    to_variant_with_options(value, &SerializeOptions::default())
+}
+
+/// Turns a rust value into a variant using the options given
+pub fn to_variant_with_options<T: Serialize + ?Sized>(
+    value: &T,
+    options: &SerializeOptions,
+) -> Result<Box<dyn GodotVariant>, SerdeError> {
+    value.serialize(ser::VariantSerializer::new(options))
+}
+
+/// Decodes bytes into a variant and reads a rust value from it
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, SerdeError> {
+    from_bytes_with_options(bytes, &DecodeOptions::default())
+}
+
+/// Decodes bytes into a variant using the options given and reads a rust value from it
+pub fn from_bytes_with_options<T: DeserializeOwned>(
+    bytes: &[u8],
+    options: &DecodeOptions,
+) -> Result<T, SerdeError> {
+    let variant = Decoder::decode_variant_with_options(bytes, options)?;
+
+    from_variant(&*variant)
+}
+
+/// Reads a rust value from a variant that has already been decoded, strings can be borrowed
+/// from the variant
+pub fn from_variant<'de, T: Deserialize<'de>>(
+    variant: &'de dyn GodotVariant,
+) -> Result<T, SerdeError> {
+    T::deserialize(de::VariantDeserializer::new(variant))
+}
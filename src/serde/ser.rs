@@ -0,0 +1,451 @@
+use ::serde::ser::{self, Serialize};
+use indexmap::IndexMap;
+
+use crate::{
+    error::SerdeError,
+    types::{
+        packed::GodotPackedByteArray,
+        primitive::{GodotBool, GodotFloat, GodotInteger, GodotNull, GodotString},
+        structures::{GodotArray, GodotDictionary},
+        variant::GodotVariant,
+    },
+};
+
+use super::{EnumRepresentation, SerializeOptions};
+
+/// Turns a rust value into a variant
+#[derive(Debug, Clone, Copy)]
+pub struct VariantSerializer<'a> {
+    options: &'a SerializeOptions,
+}
+
+impl<'a> VariantSerializer<'a> {
+    pub fn new(options: &'a SerializeOptions) -> Self {
+        Self { options }
+    }
+
+    /// An integer as a variant, godot integers are 64 bit so larger values can't be sent
+    fn int<T>(value: T) -> Result<Box<dyn GodotVariant>, SerdeError>
+    where
+        T: TryInto<i64> + std::fmt::Display + Copy,
+    {
+        let value = value.try_into().map_err(|_| {
+            <SerdeError as ser::Error>::custom(format!("{value} is too large for a godot integer"))
+        })?;
+
+        Ok(Box::new(GodotInteger::new_from_i64(value)))
+    }
+
+    /// Puts the fields of an enum variant together with the variant they belong to
+    fn enum_variant(
+        &self,
+        variant: &'static str,
+        variant_index: u32,
+        fields: Box<dyn GodotVariant>,
+    ) -> Box<dyn GodotVariant> {
+        match self.options.enums {
+            EnumRepresentation::Name => {
+                let mut dictionary = GodotDictionary::new();
+                dictionary
+                    .map
+                    .insert(Box::new(GodotString::new(variant)), fields);
+
+                Box::new(dictionary)
+            }
+            EnumRepresentation::Index => Box::new(GodotArray::new_from_vec(vec![
+                Box::new(GodotInteger::new_from_i64(variant_index as i64)),
+                fields,
+            ])),
+        }
+    }
+}
+
+impl<'a> ser::Serializer for VariantSerializer<'a> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    type SerializeSeq = SerializeArray<'a>;
+    type SerializeTuple = SerializeArray<'a>;
+    type SerializeTupleStruct = SerializeArray<'a>;
+    type SerializeTupleVariant = SerializeArray<'a>;
+    type SerializeMap = SerializeDictionary<'a>;
+    type SerializeStruct = SerializeDictionary<'a>;
+    type SerializeStructVariant = SerializeDictionary<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotBool::new(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        Self::int(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotFloat::new_from_f32(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotFloat::new_from_f64(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotString::new(v.encode_utf8(&mut [0; 4]))))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotString::new(v)))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotPackedByteArray::new(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotNull))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotNull))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Box::new(GodotNull))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        match self.options.enums {
+            EnumRepresentation::Name => Ok(Box::new(GodotString::new(variant))),
+            EnumRepresentation::Index => Self::int(variant_index),
+        }
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        let fields = value.serialize(self)?;
+
+        Ok(self.enum_variant(variant, variant_index, fields))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeArray::new(self, len.unwrap_or(0), None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(SerializeArray::new(self, len, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(SerializeArray::new(self, len, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(SerializeArray::new(
+            self,
+            len,
+            Some((variant, variant_index)),
+        ))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(SerializeDictionary::new(self, len.unwrap_or(0), None))
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(SerializeDictionary::new(self, len, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(SerializeDictionary::new(
+            self,
+            len,
+            Some((variant, variant_index)),
+        ))
+    }
+}
+
+/// Collects the values of a sequence, tuple or tuple variant into an array
+pub struct SerializeArray<'a> {
+    serializer: VariantSerializer<'a>,
+    values: Vec<Box<dyn GodotVariant>>,
+    /// The name and index of the enum variant the array holds the fields of
+    variant: Option<(&'static str, u32)>,
+}
+
+impl<'a> SerializeArray<'a> {
+    fn new(
+        serializer: VariantSerializer<'a>,
+        len: usize,
+        variant: Option<(&'static str, u32)>,
+    ) -> Self {
+        Self {
+            serializer,
+            values: Vec::with_capacity(len),
+            variant,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.values.push(value.serialize(self.serializer)?);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Box<dyn GodotVariant> {
+        match self.variant {
+            // The fields follow the index in the same array rather than being nested in another
+            Some((_, variant_index))
+                if self.serializer.options.enums == EnumRepresentation::Index =>
+            {
+                let mut values = Vec::with_capacity(self.values.len() + 1);
+                values.push(Box::new(GodotInteger::new_from_i64(variant_index as i64))
+                    as Box<dyn GodotVariant>);
+                values.extend(self.values);
+
+                Box::new(GodotArray::new_from_vec(values))
+            }
+            Some((variant, variant_index)) => self.serializer.enum_variant(
+                variant,
+                variant_index,
+                Box::new(GodotArray::new_from_vec(self.values)),
+            ),
+            None => Box::new(GodotArray::new_from_vec(self.values)),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray<'_> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray<'_> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray<'_> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray<'_> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.finish())
+    }
+}
+
+/// Collects the entries of a map, struct or struct variant into a dictionary. The fields of a
+/// struct are keyed by their name as a string
+pub struct SerializeDictionary<'a> {
+    serializer: VariantSerializer<'a>,
+    map: IndexMap<Box<dyn GodotVariant>, Box<dyn GodotVariant>>,
+    /// The key of the value that is serialized next
+    key: Option<Box<dyn GodotVariant>>,
+    /// The name and index of the enum variant the dictionary holds the fields of
+    variant: Option<(&'static str, u32)>,
+}
+
+impl<'a> SerializeDictionary<'a> {
+    fn new(
+        serializer: VariantSerializer<'a>,
+        len: usize,
+        variant: Option<(&'static str, u32)>,
+    ) -> Self {
+        Self {
+            serializer,
+            map: IndexMap::with_capacity(len),
+            key: None,
+            variant,
+        }
+    }
+
+    fn insert_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        let value = value.serialize(self.serializer)?;
+        self.map.insert(Box::new(GodotString::new(key)), value);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Box<dyn GodotVariant> {
+        let dictionary = Box::new(GodotDictionary::new_from_map(self.map));
+
+        match self.variant {
+            Some((variant, variant_index)) => {
+                self.serializer
+                    .enum_variant(variant, variant_index, dictionary)
+            }
+            None => dictionary,
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeDictionary<'_> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.key = Some(key.serialize(self.serializer)?);
+
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self.key.take().ok_or_else(|| {
+            <SerdeError as ser::Error>::custom("a map value was serialized before its key")
+        })?;
+        self.map.insert(key, value.serialize(self.serializer)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for SerializeDictionary<'_> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.insert_field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeDictionary<'_> {
+    type Ok = Box<dyn GodotVariant>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.insert_field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.finish())
+    }
+}
@@ -0,0 +1,796 @@
+//! Conversions between rust values and variants. Rust values can be turned into a variant with
+//! `From` and read back out with `TryFrom<&dyn GodotVariant>`, ints are promoted to floats the
+//! same way godot does. Structs can implement these with the `GodotVariant` derive from the
+//! `derive` feature, or only one way with the `ToVariant` and `FromVariant` derives
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    io::Write,
+};
+
+use crate::{
+    encoder::Encoder,
+    error::{ConvertError, EncodeError},
+};
+
+use super::{
+    geometry::{GodotAabb, GodotPlane, GodotRect2, GodotRect2i},
+    node_path::GodotNodePath,
+    object::GodotRid,
+    packed::{
+        GodotPackedByteArray, GodotPackedColorArray, GodotPackedFloat32Array,
+        GodotPackedFloat64Array, GodotPackedInt32Array, GodotPackedInt64Array,
+        GodotPackedStringArray, GodotPackedVector2Array, GodotPackedVector3Array,
+        GodotPackedVector4Array,
+    },
+    primitive::{GodotBool, GodotFloat, GodotInteger, GodotNull, GodotString, GodotStringName},
+    structures::{
+        GodotArray, GodotColor, GodotDictionary, GodotTypedArray, GodotVector2, GodotVector2i,
+        GodotVector3, GodotVector3i, GodotVector4, GodotVector4i,
+    },
+    transform::{GodotBasis, GodotProjection, GodotQuaternion, GodotTransform2D, GodotTransform3D},
+    variant::{AsVariant, GodotVariant, Variant},
+};
+
+#[cfg(feature = "derive")]
+pub use godot_binary_serialization_derive::{FromVariant, ToVariant};
+
+/// A rust value that can be turned into a variant
+pub trait ToVariant {
+    /// The value as a variant
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError>;
+
+    /// Turns the value into a variant and encodes it into bytes
+    fn encode(&self) -> Result<Vec<u8>, EncodeError> {
+        Encoder::encode_variant(&*self.to_variant()?)
+    }
+
+    /// Turns the value into a variant and encodes it into a writer
+    fn encode_into<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        Encoder::encode_into(&*self.to_variant()?, writer)
+    }
+}
+
+/// A rust value that can be read out of a variant
+pub trait FromVariant: Sized {
+    /// Reads the value out of a variant, this fails if the variant is not the right type
+    fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError>;
+}
+
+/// The width an integer field is sent with, chosen with `#[godot(int = 32)]` or
+/// `#[godot(int = 64)]` in the derives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntWidth {
+    Bit32,
+    Bit64,
+}
+
+/// An integer or list of integers that can be sent with a chosen width. Single integers are
+/// checked to fit in 32 bits or always written as 64 bits, godot reads a 64 bit integer with any
+/// value. Lists of integers are written as a packed int 32 or packed int 64 array
+pub trait IntVariant: Sized {
+    /// The value as a variant with the width given
+    fn to_int_variant(&self, width: IntWidth) -> Result<Box<dyn GodotVariant>, ConvertError>;
+
+    /// Reads the value out of a variant, integers outside of the width are refused
+    fn from_int_variant(variant: &dyn GodotVariant, width: IntWidth) -> Result<Self, ConvertError>;
+}
+
+/// Checks that an integer fits in the width
+fn check_width(value: i64, width: IntWidth) -> Result<i64, ConvertError> {
+    match width {
+        IntWidth::Bit32 if i32::try_from(value).is_err() => Err(ConvertError::OutOfRange {
+            value,
+            target: "32 bits",
+        }),
+        _ => Ok(value),
+    }
+}
+
+/// The values of an array or typed array, used by the derives to read tuple structs
+pub fn array_elements(
+    variant: &dyn GodotVariant,
+) -> Result<&[Box<dyn GodotVariant>], ConvertError> {
+    if let Some(array) = variant.as_var::<GodotArray>() {
+        return Ok(&array.values);
+    }
+
+    if let Some(array) = variant.as_var::<GodotTypedArray>() {
+        return Ok(&array.values);
+    }
+
+    Err(ConvertError::unexpected("an array", variant))
+}
+
+/// Reads each value of an array or packed array, the values of packed arrays are read as the
+/// variant they would be in an array
+fn array_values<T>(
+    variant: &dyn GodotVariant,
+    mut read: impl FnMut(&dyn GodotVariant) -> Result<T, ConvertError>,
+) -> Result<Vec<T>, ConvertError> {
+    if let Some(array) = variant.as_var::<GodotArray>() {
+        return array.values.iter().map(|value| read(&**value)).collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotTypedArray>() {
+        return array.values.iter().map(|value| read(&**value)).collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedByteArray>() {
+        return array
+            .values
+            .iter()
+            .map(|value| read(&GodotInteger::new_from_i64(*value as i64)))
+            .collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedInt32Array>() {
+        return array
+            .values
+            .iter()
+            .map(|value| read(&GodotInteger::new_from_i32(*value)))
+            .collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedInt64Array>() {
+        return array
+            .values
+            .iter()
+            .map(|value| read(&GodotInteger::new_from_i64(*value)))
+            .collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedFloat32Array>() {
+        return array
+            .values
+            .iter()
+            .map(|value| read(&GodotFloat::new_from_f32(*value)))
+            .collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedFloat64Array>() {
+        return array
+            .values
+            .iter()
+            .map(|value| read(&GodotFloat::new_from_f64(*value)))
+            .collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedStringArray>() {
+        return array
+            .values
+            .iter()
+            .map(|value| read(&GodotString::new(value)))
+            .collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedVector2Array>() {
+        return array.values.iter().map(|value| read(value)).collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedVector3Array>() {
+        return array.values.iter().map(|value| read(value)).collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedVector4Array>() {
+        return array.values.iter().map(|value| read(value)).collect();
+    }
+
+    if let Some(array) = variant.as_var::<GodotPackedColorArray>() {
+        return array.values.iter().map(|value| read(value)).collect();
+    }
+
+    Err(ConvertError::unexpected("an array", variant))
+}
+
+/// Reads a float, integers are promoted to floats the same way godot does when an int is given
+/// where a float is expected
+fn float_value(variant: &dyn GodotVariant) -> Result<f64, ConvertError> {
+    if let Some(int) = variant.as_var::<GodotInteger>() {
+        return Ok(int.value as f64);
+    }
+
+    GodotFloat::from_variant(variant).map(|float| float.value)
+}
+
+/// Implements the conversions for rust integers, godot integers are 64 bit
+macro_rules! int_conversions {
+    ($($int:ty),*) => {
+        $(
+            impl ToVariant for $int {
+                fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+                    // Only a u64 can be too large, every other integer fits in an i64
+                    let value =
+                        i64::try_from(*self).map_err(|_| ConvertError::TooLarge(*self as u64))?;
+
+                    Ok(Box::new(GodotInteger::new_from_i64(value)))
+                }
+            }
+
+            impl FromVariant for $int {
+                fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+                    let value = variant
+                        .as_var::<GodotInteger>()
+                        .ok_or_else(|| ConvertError::unexpected("an integer", variant))?
+                        .value;
+
+                    <$int>::try_from(value).map_err(|_| ConvertError::OutOfRange {
+                        value,
+                        target: stringify!($int),
+                    })
+                }
+            }
+
+            impl IntVariant for $int {
+                fn to_int_variant(
+                    &self,
+                    width: IntWidth,
+                ) -> Result<Box<dyn GodotVariant>, ConvertError> {
+                    let variant = self.to_variant()?;
+                    let value = check_width(*self as i64, width)?;
+
+                    Ok(match width {
+                        IntWidth::Bit32 => variant,
+                        IntWidth::Bit64 => Box::new(GodotInteger::new_64_bit(value)),
+                    })
+                }
+
+                fn from_int_variant(
+                    variant: &dyn GodotVariant,
+                    width: IntWidth,
+                ) -> Result<Self, ConvertError> {
+                    let value = Self::from_variant(variant)?;
+                    check_width(value as i64, width)?;
+
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+
+int_conversions!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+/// Implements the conversions for godot types that can be copied out of a variant
+macro_rules! godot_conversions {
+    ($($godot_type:ty => $expected:literal),* $(,)?) => {
+        $(
+            impl ToVariant for $godot_type {
+                fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+                    Ok(Box::new(self.clone()))
+                }
+            }
+
+            impl FromVariant for $godot_type {
+                fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+                    variant
+                        .as_var::<$godot_type>()
+                        .cloned()
+                        .ok_or_else(|| ConvertError::unexpected($expected, variant))
+                }
+            }
+        )*
+    };
+}
+
+godot_conversions!(
+    GodotBool => "a bool",
+    GodotInteger => "an integer",
+    GodotFloat => "a float",
+    GodotString => "a string",
+    GodotStringName => "a string name",
+    GodotNodePath => "a node path",
+    GodotRid => "a RID",
+    GodotVector2 => "a vector 2",
+    GodotVector2i => "a vector 2i",
+    GodotVector3 => "a vector 3",
+    GodotVector3i => "a vector 3i",
+    GodotVector4 => "a vector 4",
+    GodotVector4i => "a vector 4i",
+    GodotRect2 => "a rect 2",
+    GodotRect2i => "a rect 2i",
+    GodotAabb => "an AABB",
+    GodotPlane => "a plane",
+    GodotQuaternion => "a quaternion",
+    GodotBasis => "a basis",
+    GodotTransform2D => "a transform 2D",
+    GodotTransform3D => "a transform 3D",
+    GodotProjection => "a projection",
+    GodotColor => "a color",
+    GodotPackedByteArray => "a packed byte array",
+    GodotPackedInt32Array => "a packed int 32 array",
+    GodotPackedInt64Array => "a packed int 64 array",
+    GodotPackedFloat32Array => "a packed float 32 array",
+    GodotPackedFloat64Array => "a packed float 64 array",
+    GodotPackedStringArray => "a packed string array",
+    GodotPackedVector2Array => "a packed vector 2 array",
+    GodotPackedVector3Array => "a packed vector 3 array",
+    GodotPackedVector4Array => "a packed vector 4 array",
+    GodotPackedColorArray => "a packed color array",
+);
+
+impl ToVariant for bool {
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        Ok(Box::new(GodotBool::new(*self)))
+    }
+}
+
+impl FromVariant for bool {
+    fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+        GodotBool::from_variant(variant).map(|bool| bool.value)
+    }
+}
+
+impl ToVariant for f32 {
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        Ok(Box::new(GodotFloat::new_from_f32(*self)))
+    }
+}
+
+impl FromVariant for f32 {
+    fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+        float_value(variant).map(|value| value as f32)
+    }
+}
+
+impl ToVariant for f64 {
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        Ok(Box::new(GodotFloat::new_from_f64(*self)))
+    }
+}
+
+impl FromVariant for f64 {
+    fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+        float_value(variant)
+    }
+}
+
+impl ToVariant for str {
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        Ok(Box::new(GodotString::new(self)))
+    }
+}
+
+impl ToVariant for String {
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        self.as_str().to_variant()
+    }
+}
+
+impl FromVariant for String {
+    /// Strings can be read from a string or a string name
+    fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+        if let Some(string_name) = variant.as_var::<GodotStringName>() {
+            return Ok(string_name.value.clone());
+        }
+
+        GodotString::from_variant(variant).map(|string| string.value)
+    }
+}
+
+impl<T: ToVariant> ToVariant for Option<T> {
+    /// None is sent as null
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        match self {
+            Some(value) => value.to_variant(),
+            None => Ok(Box::new(GodotNull)),
+        }
+    }
+}
+
+impl<T: FromVariant> FromVariant for Option<T> {
+    fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+        if variant.as_var::<GodotNull>().is_some() {
+            return Ok(None);
+        }
+
+        T::from_variant(variant).map(Some)
+    }
+}
+
+impl<T: IntVariant> IntVariant for Option<T> {
+    fn to_int_variant(&self, width: IntWidth) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        match self {
+            Some(value) => value.to_int_variant(width),
+            None => Ok(Box::new(GodotNull)),
+        }
+    }
+
+    fn from_int_variant(variant: &dyn GodotVariant, width: IntWidth) -> Result<Self, ConvertError> {
+        if variant.as_var::<GodotNull>().is_some() {
+            return Ok(None);
+        }
+
+        T::from_int_variant(variant, width).map(Some)
+    }
+}
+
+impl<T: ToVariant> ToVariant for Vec<T> {
+    /// A vec is sent as an array
+    fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        let values = self
+            .iter()
+            .map(ToVariant::to_variant)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Box::new(GodotArray::new_from_vec(values)))
+    }
+}
+
+impl<T: FromVariant> FromVariant for Vec<T> {
+    /// A vec can be read from an array or any packed array
+    fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+        array_values(variant, T::from_variant)
+    }
+}
+
+impl<T: IntVariant + Copy + Into<i64>> IntVariant for Vec<T> {
+    fn to_int_variant(&self, width: IntWidth) -> Result<Box<dyn GodotVariant>, ConvertError> {
+        let values = self.iter().map(|value| (*value).into());
+
+        match width {
+            IntWidth::Bit32 => {
+                let values = values
+                    .map(|value| {
+                        i32::try_from(value).map_err(|_| ConvertError::OutOfRange {
+                            value,
+                            target: "32 bits",
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Box::new(GodotPackedInt32Array::new(values)))
+            }
+            IntWidth::Bit64 => Ok(Box::new(GodotPackedInt64Array::new(values.collect()))),
+        }
+    }
+
+    fn from_int_variant(variant: &dyn GodotVariant, width: IntWidth) -> Result<Self, ConvertError> {
+        array_values(variant, |value| T::from_int_variant(value, width))
+    }
+}
+
+/// Implements the conversions for maps, they are sent as a dictionary
+macro_rules! map_conversions {
+    ($($map:ident<K: $($bound:ident)+>),*) => {
+        $(
+            impl<K: ToVariant, V: ToVariant> ToVariant for $map<K, V> {
+                fn to_variant(&self) -> Result<Box<dyn GodotVariant>, ConvertError> {
+                    let mut dictionary = GodotDictionary::new();
+                    for (key, value) in self.iter() {
+                        dictionary.map.insert(key.to_variant()?, value.to_variant()?);
+                    }
+
+                    Ok(Box::new(dictionary))
+                }
+            }
+
+            impl<K: FromVariant $(+ $bound)+, V: FromVariant> FromVariant for $map<K, V> {
+                fn from_variant(variant: &dyn GodotVariant) -> Result<Self, ConvertError> {
+                    let dictionary = variant
+                        .as_var::<GodotDictionary>()
+                        .ok_or_else(|| ConvertError::unexpected("a dictionary", variant))?;
+
+                    dictionary
+                        .map
+                        .iter()
+                        .map(|(key, value)| Ok((K::from_variant(&**key)?, V::from_variant(&**value)?)))
+                        .collect()
+                }
+            }
+
+            impl<K: Into<Variant>, V: Into<Variant>> From<$map<K, V>> for Variant {
+                fn from(map: $map<K, V>) -> Self {
+                    let map = map
+                        .into_iter()
+                        .map(|(key, value)| (key.into().into_boxed(), value.into().into_boxed()))
+                        .collect();
+
+                    Variant::Dictionary(GodotDictionary::new_from_map(map))
+                }
+            }
+
+            impl<K: Into<Variant>, V: Into<Variant>> From<$map<K, V>> for Box<dyn GodotVariant> {
+                fn from(map: $map<K, V>) -> Self {
+                    Variant::from(map).into_boxed()
+                }
+            }
+
+            impl<K: FromVariant $(+ $bound)+, V: FromVariant> TryFrom<&dyn GodotVariant>
+                for $map<K, V>
+            {
+                type Error = ConvertError;
+
+                fn try_from(variant: &dyn GodotVariant) -> Result<Self, Self::Error> {
+                    Self::from_variant(variant)
+                }
+            }
+        )*
+    };
+}
+
+map_conversions!(HashMap<K: Eq Hash>, BTreeMap<K: Ord>);
+
+/// Implements `From` for rust values that always fit in a godot type, going through the godot
+/// type so that `From<i64>` gives the same variant as `GodotInteger::from`
+macro_rules! from_native {
+    ($($native:ty => $godot_type:ident::$new:ident),* $(,)?) => {
+        $(
+            impl From<$native> for $godot_type {
+                fn from(value: $native) -> Self {
+                    $godot_type::$new(value.into())
+                }
+            }
+
+            impl From<$native> for Variant {
+                fn from(value: $native) -> Self {
+                    Variant::from($godot_type::from(value))
+                }
+            }
+
+            impl From<$native> for Box<dyn GodotVariant> {
+                fn from(value: $native) -> Self {
+                    Box::new($godot_type::from(value))
+                }
+            }
+        )*
+    };
+}
+
+from_native!(
+    bool => GodotBool::new,
+    i8 => GodotInteger::new_from_i64,
+    i16 => GodotInteger::new_from_i64,
+    i32 => GodotInteger::new_from_i64,
+    i64 => GodotInteger::new_from_i64,
+    u8 => GodotInteger::new_from_i64,
+    u16 => GodotInteger::new_from_i64,
+    u32 => GodotInteger::new_from_i64,
+    f32 => GodotFloat::new_from_f32,
+    f64 => GodotFloat::new_from_f64,
+    &str => GodotString::new,
+);
+
+impl TryFrom<u64> for GodotInteger {
+    type Error = ConvertError;
+
+    /// Godot integers are signed so values larger than [i64::MAX] can't be sent
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        i64::try_from(value)
+            .map(GodotInteger::new_from_i64)
+            .map_err(|_| ConvertError::TooLarge(value))
+    }
+}
+
+impl TryFrom<u64> for Variant {
+    type Error = ConvertError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        GodotInteger::try_from(value).map(Variant::from)
+    }
+}
+
+impl TryFrom<u64> for Box<dyn GodotVariant> {
+    type Error = ConvertError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        GodotInteger::try_from(value).map(|value| Box::new(value) as Box<dyn GodotVariant>)
+    }
+}
+
+impl From<String> for GodotString {
+    fn from(value: String) -> Self {
+        GodotString::new(&value)
+    }
+}
+
+impl From<String> for Variant {
+    fn from(value: String) -> Self {
+        Variant::from(GodotString::from(value))
+    }
+}
+
+impl From<String> for Box<dyn GodotVariant> {
+    fn from(value: String) -> Self {
+        Box::new(GodotString::from(value))
+    }
+}
+
+impl<T: Into<Variant>> From<Option<T>> for Variant {
+    /// None is null
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Variant::Nil, Into::into)
+    }
+}
+
+impl<T: Into<Variant>> From<Option<T>> for Box<dyn GodotVariant> {
+    fn from(value: Option<T>) -> Self {
+        Variant::from(value).into_boxed()
+    }
+}
+
+impl<T: Into<Variant>> From<Vec<T>> for Variant {
+    /// A vec is an array
+    fn from(values: Vec<T>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|value| value.into().into_boxed())
+            .collect();
+
+        Variant::Array(GodotArray::new_from_vec(values))
+    }
+}
+
+impl<T: Into<Variant>> From<Vec<T>> for Box<dyn GodotVariant> {
+    fn from(values: Vec<T>) -> Self {
+        Variant::from(values).into_boxed()
+    }
+}
+
+/// Implements `TryFrom<&dyn GodotVariant>` for rust values that implement [FromVariant]
+macro_rules! try_from_variant {
+    ($($native:ty),* $(,)?) => {
+        $(
+            impl TryFrom<&dyn GodotVariant> for $native {
+                type Error = ConvertError;
+
+                fn try_from(variant: &dyn GodotVariant) -> Result<Self, Self::Error> {
+                    Self::from_variant(variant)
+                }
+            }
+        )*
+    };
+}
+
+try_from_variant!(bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, String);
+
+impl<T: FromVariant> TryFrom<&dyn GodotVariant> for Option<T> {
+    type Error = ConvertError;
+
+    fn try_from(variant: &dyn GodotVariant) -> Result<Self, Self::Error> {
+        Self::from_variant(variant)
+    }
+}
+
+impl<T: FromVariant> TryFrom<&dyn GodotVariant> for Vec<T> {
+    type Error = ConvertError;
+
+    fn try_from(variant: &dyn GodotVariant) -> Result<Self, Self::Error> {
+        Self::from_variant(variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        error::ConvertError,
+        types::{
+            packed::{GodotPackedInt32Array, GodotPackedInt64Array},
+            primitive::{GodotFloat, GodotInteger, GodotNull, GodotString},
+            structures::GodotArray,
+            variant::{AsVariant, GodotVariant, Variant},
+        },
+    };
+
+    use super::{FromVariant, IntVariant, IntWidth, ToVariant};
+
+    #[test]
+    fn integers_are_range_checked() {
+        let variant = GodotInteger::new_from_i64(300);
+
+        let result = u8::from_variant(&variant);
+        let expected = ConvertError::OutOfRange {
+            value: 300,
+            target: "u8",
+        };
+        assert_eq!(
+            Err(expected.clone()),
+            result,
+            "Expected {:?} but got {:?}",
+            expected,
+            result
+        );
+
+        let variant = GodotInteger::new_from_i64(i64::MAX);
+        let result = i64::from_int_variant(&variant, IntWidth::Bit32);
+        assert!(result.is_err(), "Expected an error but got {:?}", result);
+
+        // Godot integers are signed so a u64 above i64::MAX can't be sent
+        let expected = ConvertError::TooLarge(u64::MAX);
+        let result = u64::MAX.to_variant().map(|_| ());
+        assert_eq!(
+            Err(expected.clone()),
+            result,
+            "Expected {:?} but got {:?}",
+            expected,
+            result
+        );
+        let expected = ConvertError::TooLarge(i64::MAX as u64 + 1);
+        let result = Variant::try_from(i64::MAX as u64 + 1).map(|_| ());
+        assert_eq!(
+            Err(expected.clone()),
+            result,
+            "Expected {:?} but got {:?}",
+            expected,
+            result
+        );
+
+        let variant = Box::<dyn GodotVariant>::try_from(i64::MAX as u64).unwrap();
+        let result = u64::try_from(&*variant);
+        assert_eq!(
+            Ok(i64::MAX as u64),
+            result,
+            "Expected {:?} but got {:?}",
+            i64::MAX,
+            result
+        );
+        let result = u64::from_variant(&GodotInteger::new_from_i64(-1));
+        assert!(result.is_err(), "Expected an error but got {:?}", result);
+    }
+
+    #[test]
+    fn lists_from_packed_arrays() {
+        let variant = GodotPackedInt64Array::new(vec![1, 2, 3]);
+        let values = Vec::<u16>::from_variant(&variant).unwrap();
+        assert_eq!(
+            vec![1, 2, 3],
+            values,
+            "Expected {:?} but got {:?}",
+            vec![1, 2, 3],
+            values
+        );
+
+        let variant = vec![1i64, 2, 3].to_int_variant(IntWidth::Bit32).unwrap();
+        assert!(variant.as_var::<GodotPackedInt32Array>().is_some());
+
+        let result = Vec::<String>::from_variant(&GodotString::new("abc"));
+        assert!(result.is_err(), "Expected an error but got {:?}", result);
+
+        let variant = vec!["a".to_owned()].to_variant().unwrap();
+        let values = Vec::<String>::from_variant(&*variant).unwrap();
+        assert_eq!(vec!["a".to_owned()], values);
+    }
+
+    #[test]
+    fn native_types_into_variants() {
+        let variant = Variant::from(vec![Some(1i64), None]);
+        let expected = Variant::from(GodotArray::new_from_vec(vec![
+            Box::new(GodotInteger::new_from_i64(1)),
+            Box::new(GodotNull),
+        ]));
+        assert!(
+            variant.as_dyn().variant_eq(expected.as_dyn()),
+            "Expected {:?} but got {:?}",
+            expected,
+            variant
+        );
+
+        let map = HashMap::from([("hp", 100)]);
+        let variant = Box::<dyn GodotVariant>::from(map.clone());
+        let decoded = HashMap::<String, i32>::try_from(&*variant).unwrap();
+        assert_eq!(decoded.get("hp"), Some(&100));
+
+        let variant = Box::<dyn GodotVariant>::from("player");
+        let decoded = String::try_from(&*variant).unwrap();
+        assert_eq!(
+            "player", decoded,
+            "Expected {:?} but got {:?}",
+            "player", decoded
+        );
+    }
+
+    #[test]
+    fn ints_promote_to_floats() {
+        let variant = GodotInteger::new_from_i32(3);
+        let value = f32::try_from(&variant as &dyn GodotVariant).unwrap();
+        assert_eq!(3.0, value, "Expected {:?} but got {:?}", 3.0, value);
+
+        // Floats are not truncated into ints
+        let variant = GodotFloat::new_from_f64(3.5);
+        let result = i32::try_from(&variant as &dyn GodotVariant);
+        assert!(result.is_err(), "Expected an error but got {:?}", result);
+    }
+}
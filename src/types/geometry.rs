@@ -0,0 +1,415 @@
+use std::hash::{Hash, Hasher};
+
+use super::{
+    eq_as_variant, hash_reals, real_size, reals_eq,
+    structures::{GodotVector2, GodotVector2i, GodotVector3},
+    variant::{AsVariant, GodotVariant},
+    GodotTypeIndex, TYPE_PADDING,
+};
+
+/// A Rect2 from godot, an axis aligned rectangle made up of a position and a size. Godot builds
+/// with double precision write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotRect2 {
+    pub position: GodotVector2,
+    pub size: GodotVector2,
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotRect2 {
+    const COMPONENTS: usize = 4;
+
+    pub fn new(position: GodotVector2, size: GodotVector2) -> Self {
+        Self {
+            position,
+            size,
+            double_precision: false,
+        }
+    }
+
+    /// The same rectangle written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them
+    pub fn components(&self) -> [f64; 4] {
+        [self.position.x, self.position.y, self.size.x, self.size.y]
+    }
+
+    /// The corner of the rectangle opposite to the position
+    pub fn end(&self) -> GodotVector2 {
+        GodotVector2::new(self.position.x + self.size.x, self.position.y + self.size.y)
+    }
+
+    /// The area of the rectangle
+    pub fn area(&self) -> f64 {
+        self.size.x * self.size.y
+    }
+
+    /// Checks if a point is inside of the rectangle. Like godot, points on the end edges are not
+    /// inside of the rectangle
+    pub fn contains(&self, point: GodotVector2) -> bool {
+        let end = self.end();
+
+        point.x >= self.position.x
+            && point.y >= self.position.y
+            && point.x < end.x
+            && point.y < end.y
+    }
+
+    /// Checks if the rectangle overlaps another rectangle. Rectangles that only share an edge do
+    /// not intersect
+    pub fn intersects(&self, other: &GodotRect2) -> bool {
+        let end = self.end();
+        let other_end = other.end();
+
+        self.position.x < other_end.x
+            && end.x > other.position.x
+            && self.position.y < other_end.y
+            && end.y > other.position.y
+    }
+}
+
+impl GodotVariant for GodotRect2 {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Rect2
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotRect2>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Rect2i from godot, an axis aligned rectangle of integers made up of a position and a size
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct GodotRect2i {
+    pub position: GodotVector2i,
+    pub size: GodotVector2i,
+}
+
+impl GodotRect2i {
+    const BIT_SIZE: usize = 16;
+
+    pub fn new(position: GodotVector2i, size: GodotVector2i) -> Self {
+        Self { position, size }
+    }
+
+    /// The corner of the rectangle opposite to the position
+    pub fn end(&self) -> GodotVector2i {
+        GodotVector2i::new(self.position.x + self.size.x, self.position.y + self.size.y)
+    }
+
+    /// The area of the rectangle
+    pub fn area(&self) -> i32 {
+        self.size.x * self.size.y
+    }
+
+    /// Checks if a point is inside of the rectangle. Like godot, points on the end edges are not
+    /// inside of the rectangle
+    pub fn contains(&self, point: GodotVector2i) -> bool {
+        let end = self.end();
+
+        point.x >= self.position.x
+            && point.y >= self.position.y
+            && point.x < end.x
+            && point.y < end.y
+    }
+
+    /// Checks if the rectangle overlaps another rectangle. Rectangles that only share an edge do
+    /// not intersect
+    pub fn intersects(&self, other: &GodotRect2i) -> bool {
+        let end = self.end();
+        let other_end = other.end();
+
+        self.position.x < other_end.x
+            && end.x > other.position.x
+            && self.position.y < other_end.y
+            && end.y > other.position.y
+    }
+}
+
+impl GodotVariant for GodotRect2i {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::BIT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Rect2I
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotRect2i>() {
+            self == other
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+/// An AABB from godot, an axis aligned bounding box made up of a position and a size. Godot
+/// builds with double precision write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotAabb {
+    pub position: GodotVector3,
+    pub size: GodotVector3,
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotAabb {
+    const COMPONENTS: usize = 6;
+
+    pub fn new(position: GodotVector3, size: GodotVector3) -> Self {
+        Self {
+            position,
+            size,
+            double_precision: false,
+        }
+    }
+
+    /// The same box written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them
+    pub fn components(&self) -> [f64; 6] {
+        [
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            self.size.x,
+            self.size.y,
+            self.size.z,
+        ]
+    }
+
+    /// The corner of the box opposite to the position
+    pub fn end(&self) -> GodotVector3 {
+        GodotVector3::new(
+            self.position.x + self.size.x,
+            self.position.y + self.size.y,
+            self.position.z + self.size.z,
+        )
+    }
+
+    /// The volume of the box
+    pub fn volume(&self) -> f64 {
+        self.size.x * self.size.y * self.size.z
+    }
+
+    /// Checks if a point is inside of the box. Like godot, points on any face of the box are
+    /// inside of the box
+    pub fn contains(&self, point: GodotVector3) -> bool {
+        let end = self.end();
+
+        point.x >= self.position.x
+            && point.y >= self.position.y
+            && point.z >= self.position.z
+            && point.x <= end.x
+            && point.y <= end.y
+            && point.z <= end.z
+    }
+
+    /// Checks if the box overlaps another box. Boxes that only share a face do not intersect
+    pub fn intersects(&self, other: &GodotAabb) -> bool {
+        let end = self.end();
+        let other_end = other.end();
+
+        self.position.x < other_end.x
+            && end.x > other.position.x
+            && self.position.y < other_end.y
+            && end.y > other.position.y
+            && self.position.z < other_end.z
+            && end.z > other.position.z
+    }
+}
+
+impl GodotVariant for GodotAabb {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Aabb
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotAabb>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Plane from godot, made up of a normal and the distance of the plane from the origin. Godot
+/// builds with double precision write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotPlane {
+    pub normal: GodotVector3,
+    pub d: f64,
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotPlane {
+    const COMPONENTS: usize = 4;
+
+    pub fn new(normal: GodotVector3, d: f64) -> Self {
+        Self {
+            normal,
+            d,
+            double_precision: false,
+        }
+    }
+
+    /// The same plane written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them
+    pub fn components(&self) -> [f64; 4] {
+        [self.normal.x, self.normal.y, self.normal.z, self.d]
+    }
+
+    /// The signed distance from the plane to a point, points above the plane have a positive
+    /// distance
+    pub fn distance_to(&self, point: GodotVector3) -> f64 {
+        self.normal.x * point.x + self.normal.y * point.y + self.normal.z * point.z - self.d
+    }
+
+    /// Checks if a point is above the plane
+    pub fn is_point_over(&self, point: GodotVector3) -> bool {
+        self.distance_to(point) > 0.0
+    }
+}
+
+impl GodotVariant for GodotPlane {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Plane
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPlane>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+eq_as_variant!(GodotRect2, GodotAabb, GodotPlane);
+
+#[cfg(test)]
+mod tests {
+    use crate::types::structures::{GodotVector2, GodotVector3};
+
+    use super::{GodotAabb, GodotRect2};
+
+    #[test]
+    fn rect2_contains_and_intersects() {
+        let rect = GodotRect2::new(GodotVector2::new(0.0, 0.0), GodotVector2::new(10.0, 5.0));
+
+        assert!(rect.contains(GodotVector2::new(0.0, 0.0)));
+        assert!(rect.contains(GodotVector2::new(9.5, 4.5)));
+        assert!(!rect.contains(GodotVector2::new(10.0, 2.0)));
+
+        let overlapping = GodotRect2::new(GodotVector2::new(5.0, 4.0), GodotVector2::new(2.0, 2.0));
+        let touching = GodotRect2::new(GodotVector2::new(10.0, 0.0), GodotVector2::new(2.0, 2.0));
+
+        assert!(rect.intersects(&overlapping));
+        assert!(!rect.intersects(&touching));
+    }
+
+    #[test]
+    fn aabb_contains_and_intersects() {
+        let aabb = GodotAabb::new(
+            GodotVector3::new(-1.0, -1.0, -1.0),
+            GodotVector3::new(2.0, 2.0, 2.0),
+        );
+
+        assert!(aabb.contains(GodotVector3::new(1.0, 0.0, -1.0)));
+        assert!(!aabb.contains(GodotVector3::new(0.0, 1.5, 0.0)));
+
+        let other = GodotAabb::new(
+            GodotVector3::new(0.5, 0.5, 0.5),
+            GodotVector3::new(1.0, 1.0, 1.0),
+        );
+
+        assert!(aabb.intersects(&other));
+        assert_eq!(aabb.volume(), 8.0);
+    }
+}
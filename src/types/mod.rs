@@ -1,17 +1,149 @@
+pub mod convert;
+pub mod geometry;
+pub mod node_path;
+pub mod object;
+pub mod packed;
 pub mod primitive;
 pub mod structures;
+pub mod transform;
 pub mod variant;
 
+use std::hash::Hasher;
+
+use variant::GodotVariant;
+
+use crate::error::TypeMismatchError;
+
 pub const TYPE_PADDING: u8 = 4;
 
-/// The different serialization flags from Godot's binary serialization
-#[derive(PartialEq, Eq, Debug)]
+/// Implements [PartialEq] with [GodotVariant::variant_eq] for types that hold reals, so comparing
+/// two values gives the same answer as comparing them as variants. Whether the reals are written
+/// with double precision is not part of the value, and NaN equals NaN like it does in godot
+macro_rules! eq_as_variant {
+    ($($godot_type:ty),*) => {
+        $(
+            impl PartialEq for $godot_type {
+                fn eq(&self, other: &Self) -> bool {
+                    GodotVariant::variant_eq(self, other)
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use eq_as_variant;
+
+/// The size of the length that comes before a string
+pub(crate) const STRING_LENGTH_SIZE: usize = 4;
+
+/// The size of a string once encoded, the length and the padded bytes of the string
+pub(crate) fn encoded_string_length(string: &str) -> usize {
+    let length = string.len();
+    // Pad 4 bytes because godot
+    let pad = (4 - (length % 4)) % 4;
+
+    STRING_LENGTH_SIZE + length + pad
+}
+
+/// The size of a real component such as the x of a vector. Godot builds with double precision
+/// write reals as 64 bit floats
+pub(crate) fn real_size(double_precision: bool) -> usize {
+    if double_precision {
+        8
+    } else {
+        4
+    }
+}
+
+/// Compares two reals the way godot does for variants, NaN is equal to NaN so a NaN key can
+/// still be found in a dictionary
+pub(crate) fn real_eq(a: f64, b: f64) -> bool {
+    a == b || (a.is_nan() && b.is_nan())
+}
+
+/// Compares the components of two reals with [real_eq]
+pub(crate) fn reals_eq(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| real_eq(*a, *b))
+}
+
+/// Hashes a real so reals that are equal with [real_eq] hash the same, 0.0 and -0.0 are equal
+/// and so is every NaN
+pub(crate) fn hash_real(real: f64, state: &mut dyn Hasher) {
+    let bits = if real == 0.0 {
+        0
+    } else if real.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        real.to_bits()
+    };
+
+    state.write_u64(bits);
+}
+
+/// Hashes the components of a real with [hash_real]
+pub(crate) fn hash_reals(reals: &[f64], state: &mut dyn Hasher) {
+    for real in reals {
+        hash_real(*real, state);
+    }
+}
+
+/// The different serialization flags from Godot's binary serialization. The flags are stored in
+/// the upper 16 bits of a variant's header and their meaning depends on the type of the variant
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum SerializeFlag {
     /// The encoder has no flags
-    None = 0,
+    None,
     /// Used for integers and floats, shows whether they are represented as a 64 bit or 32 bit
     /// value. 0 = 32 bit, 1 = 64 Bit
-    Bit64 = 1,
+    Bit64,
+    /// Used for arrays, describes how the element type of a typed array is encoded
+    TypedArray(ContainerTypeKind),
+    /// Used for dictionaries, describes how the key and value types of a typed dictionary are
+    /// encoded
+    TypedDictionary {
+        key: ContainerTypeKind,
+        value: ContainerTypeKind,
+    },
+    /// Used for objects, shows the object was sent as its instance id instead of its class name
+    /// and properties
+    ObjectAsId,
+}
+
+impl SerializeFlag {
+    /// The flag as it is written into the upper 16 bits of a variant's header
+    pub fn bits(&self) -> u16 {
+        match self {
+            SerializeFlag::None => 0,
+            SerializeFlag::Bit64 => 1,
+            SerializeFlag::ObjectAsId => 1,
+            SerializeFlag::TypedArray(kind) => *kind as u16,
+            SerializeFlag::TypedDictionary { key, value } => *key as u16 | (*value as u16) << 2,
+        }
+    }
+
+    /// Interprets the flag bits of a header based on the type of the variant they belong to
+    pub fn from_bits(type_idx: &GodotTypeIndex, bits: u16) -> SerializeFlag {
+        match type_idx {
+            GodotTypeIndex::Array => match ContainerTypeKind::from_bits(bits) {
+                ContainerTypeKind::None => SerializeFlag::None,
+                kind => SerializeFlag::TypedArray(kind),
+            },
+            GodotTypeIndex::Dictionary => {
+                let key = ContainerTypeKind::from_bits(bits);
+                let value = ContainerTypeKind::from_bits(bits >> 2);
+
+                if key == ContainerTypeKind::None && value == ContainerTypeKind::None {
+                    SerializeFlag::None
+                } else {
+                    SerializeFlag::TypedDictionary { key, value }
+                }
+            }
+            GodotTypeIndex::Object if bits & 1 == 1 => SerializeFlag::ObjectAsId,
+            GodotTypeIndex::Object => SerializeFlag::None,
+            _ if bits & 1 == 1 => SerializeFlag::Bit64,
+            _ => SerializeFlag::None,
+        }
+    }
 }
 
 impl TryFrom<u16> for SerializeFlag {
@@ -26,8 +158,135 @@ impl TryFrom<u16> for SerializeFlag {
     }
 }
 
+/// Describes how the element type of a typed container is written after the header
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ContainerTypeKind {
+    /// The container is untyped
+    None = 0,
+    /// The element type is a builtin type, written as its type index
+    Builtin = 1,
+    /// The element type is an engine class, written as the class name
+    ClassName = 2,
+    /// The element type is a script, written as the path to the script
+    Script = 3,
+}
+
+impl ContainerTypeKind {
+    /// Gets the kind from the lowest 2 bits
+    pub fn from_bits(bits: u16) -> ContainerTypeKind {
+        match bits & 0b11 {
+            1 => ContainerTypeKind::Builtin,
+            2 => ContainerTypeKind::ClassName,
+            3 => ContainerTypeKind::Script,
+            _ => ContainerTypeKind::None,
+        }
+    }
+}
+
+/// The element type of a typed container, for example the `int` in `Array[int]`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ContainerType {
+    /// The builtin type of the elements, this is [Object](GodotTypeIndex::Object) for class names
+    /// and scripts
+    pub builtin_type: GodotTypeIndex,
+    /// The name of the engine class the elements must be
+    pub class_name: Option<String>,
+    /// The path of the script the elements must be
+    pub script: Option<String>,
+}
+
+impl ContainerType {
+    /// A container type that is a builtin type such as `int` or `Vector3`
+    pub fn builtin(builtin_type: GodotTypeIndex) -> Self {
+        Self {
+            builtin_type,
+            class_name: None,
+            script: None,
+        }
+    }
+
+    /// A container type that is an engine class such as `Node`
+    pub fn class_name(class_name: &str) -> Self {
+        Self {
+            builtin_type: GodotTypeIndex::Object,
+            class_name: Some(class_name.to_owned()),
+            script: None,
+        }
+    }
+
+    /// A container type that is a script such as `res://player.gd`
+    pub fn script(path: &str) -> Self {
+        Self {
+            builtin_type: GodotTypeIndex::Object,
+            class_name: None,
+            script: Some(path.to_owned()),
+        }
+    }
+
+    /// How the container type is written in the header flags
+    pub fn kind(&self) -> ContainerTypeKind {
+        if self.builtin_type == GodotTypeIndex::Nil {
+            ContainerTypeKind::None
+        } else if self.script.is_some() {
+            ContainerTypeKind::Script
+        } else if self.class_name.is_some() {
+            ContainerTypeKind::ClassName
+        } else {
+            ContainerTypeKind::Builtin
+        }
+    }
+
+    /// The amount of bytes the container type takes up after the header once encoded
+    pub(crate) fn encoded_length(&self) -> usize {
+        match self.kind() {
+            ContainerTypeKind::None => 0,
+            ContainerTypeKind::Builtin => 4,
+            ContainerTypeKind::ClassName => {
+                encoded_string_length(self.class_name.as_deref().unwrap_or_default())
+            }
+            ContainerTypeKind::Script => {
+                encoded_string_length(self.script.as_deref().unwrap_or_default())
+            }
+        }
+    }
+
+    /// Checks if a variant can be stored in a container of this type. Object containers can also
+    /// hold null
+    pub fn matches(&self, variant: &dyn GodotVariant) -> bool {
+        let type_idx = variant.type_index();
+
+        self.builtin_type == GodotTypeIndex::Nil
+            || self.builtin_type == type_idx
+            || (self.builtin_type == GodotTypeIndex::Object && type_idx == GodotTypeIndex::Nil)
+    }
+
+    /// Checks that a variant can be stored in a container of this type, returning the mismatch if
+    /// it can't
+    pub fn check(&self, variant: &dyn GodotVariant) -> Result<(), TypeMismatchError> {
+        if !self.matches(variant) {
+            return Err(TypeMismatchError {
+                expected: self.clone(),
+                found: variant.type_index(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The version of Godot's binary serialization. The layout of most types is the same between
+/// versions but the type ids in the header are different
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum ProtocolVersion {
+    /// Godot 3.x, which has no typed containers and doesn't send the id of a RID
+    Godot3,
+    /// Godot 4.x
+    #[default]
+    Godot4,
+}
+
 /// The Godot type indexes based on Godot's binary serialization API
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub enum GodotTypeIndex {
     Nil = 0,
     Bool = 1,
@@ -58,6 +317,70 @@ pub enum GodotTypeIndex {
     Signal = 26,
     Dictionary = 27,
     Array = 28,
+    PackedByteArray = 29,
+    PackedInt32Array = 30,
+    PackedInt64Array = 31,
+    PackedFloat32Array = 32,
+    PackedFloat64Array = 33,
+    PackedStringArray = 34,
+    PackedVector2Array = 35,
+    PackedVector3Array = 36,
+    PackedColorArray = 37,
+    PackedVector4Array = 38,
+}
+
+impl GodotTypeIndex {
+    /// The types in the order of their ids in godot 3, godot 3 has fewer types than godot 4 and
+    /// numbers them differently
+    const GODOT_3_TYPES: [GodotTypeIndex; 27] = [
+        GodotTypeIndex::Nil,
+        GodotTypeIndex::Bool,
+        GodotTypeIndex::Integer,
+        GodotTypeIndex::Float,
+        GodotTypeIndex::String,
+        GodotTypeIndex::Vector2,
+        GodotTypeIndex::Rect2,
+        GodotTypeIndex::Vector3,
+        GodotTypeIndex::Transform2D,
+        GodotTypeIndex::Plane,
+        GodotTypeIndex::Quaternion,
+        GodotTypeIndex::Aabb,
+        GodotTypeIndex::Basis,
+        GodotTypeIndex::Transform3D,
+        GodotTypeIndex::Color,
+        GodotTypeIndex::NodePath,
+        GodotTypeIndex::RID,
+        GodotTypeIndex::Object,
+        GodotTypeIndex::Dictionary,
+        GodotTypeIndex::Array,
+        GodotTypeIndex::PackedByteArray,
+        GodotTypeIndex::PackedInt32Array,
+        GodotTypeIndex::PackedFloat32Array,
+        GodotTypeIndex::PackedStringArray,
+        GodotTypeIndex::PackedVector2Array,
+        GodotTypeIndex::PackedVector3Array,
+        GodotTypeIndex::PackedColorArray,
+    ];
+
+    /// The id the type is written with in the header of a protocol version. This is None when the
+    /// type does not exist in that version of godot
+    pub fn id(&self, protocol: ProtocolVersion) -> Option<u16> {
+        match protocol {
+            ProtocolVersion::Godot3 => Self::GODOT_3_TYPES
+                .iter()
+                .position(|type_idx| type_idx == self)
+                .map(|id| id as u16),
+            ProtocolVersion::Godot4 => Some(*self as u16),
+        }
+    }
+
+    /// Gets the type from the id in the header of a protocol version
+    pub fn from_id(id: u16, protocol: ProtocolVersion) -> Option<GodotTypeIndex> {
+        match protocol {
+            ProtocolVersion::Godot3 => Self::GODOT_3_TYPES.get(id as usize).copied(),
+            ProtocolVersion::Godot4 => GodotTypeIndex::try_from(id).ok(),
+        }
+    }
 }
 
 impl TryFrom<u16> for GodotTypeIndex {
@@ -93,6 +416,16 @@ impl TryFrom<u16> for GodotTypeIndex {
             26 => Ok(GodotTypeIndex::Signal),
             27 => Ok(GodotTypeIndex::Dictionary),
             28 => Ok(GodotTypeIndex::Array),
+            29 => Ok(GodotTypeIndex::PackedByteArray),
+            30 => Ok(GodotTypeIndex::PackedInt32Array),
+            31 => Ok(GodotTypeIndex::PackedInt64Array),
+            32 => Ok(GodotTypeIndex::PackedFloat32Array),
+            33 => Ok(GodotTypeIndex::PackedFloat64Array),
+            34 => Ok(GodotTypeIndex::PackedStringArray),
+            35 => Ok(GodotTypeIndex::PackedVector2Array),
+            36 => Ok(GodotTypeIndex::PackedVector3Array),
+            37 => Ok(GodotTypeIndex::PackedColorArray),
+            38 => Ok(GodotTypeIndex::PackedVector4Array),
             _ => Err(()),
         }
     }
@@ -0,0 +1,136 @@
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use super::{
+    encoded_string_length,
+    variant::{AsVariant, GodotVariant},
+    GodotTypeIndex, TYPE_PADDING,
+};
+
+/// A NodePath from godot, a path to a node and optionally a property of that node such as
+/// `/root/Main:position`. The names are the nodes in the path and the subnames are the properties
+/// after each `:`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct GodotNodePath {
+    pub names: Vec<String>,
+    pub subnames: Vec<String>,
+    /// Whether the path starts from the root of the scene tree
+    pub absolute: bool,
+}
+
+impl GodotNodePath {
+    /// The name count, subname count and flags that come after the header
+    const INFO_SIZE: usize = 12;
+
+    pub fn new(names: Vec<String>, subnames: Vec<String>, absolute: bool) -> Self {
+        Self {
+            names,
+            subnames,
+            absolute,
+        }
+    }
+
+    /// Whether the path has no names or subnames
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.subnames.is_empty()
+    }
+}
+
+impl Display for GodotNodePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.absolute {
+            write!(f, "/")?;
+        }
+
+        write!(f, "{}", self.names.join("/"))?;
+
+        for subname in self.subnames.iter() {
+            write!(f, ":{subname}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for GodotNodePath {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let absolute = s.starts_with('/');
+        let (path, subpath) = s.split_once(':').unwrap_or((s, ""));
+
+        let names = path
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .map(str::to_owned)
+            .collect();
+        let subnames = subpath
+            .split(':')
+            .filter(|subname| !subname.is_empty())
+            .map(str::to_owned)
+            .collect();
+
+        Ok(Self::new(names, subnames, absolute))
+    }
+}
+
+impl GodotVariant for GodotNodePath {
+    fn byte_length(&self) -> usize {
+        let strings_size: usize = self
+            .names
+            .iter()
+            .chain(self.subnames.iter())
+            .map(|name| encoded_string_length(name))
+            .sum();
+
+        TYPE_PADDING as usize + Self::INFO_SIZE + strings_size
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::NodePath
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotNodePath>() {
+            self == other
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GodotNodePath;
+
+    #[test]
+    fn node_path_from_str() {
+        let path: GodotNodePath = "/root/Main:position:x".parse().unwrap();
+
+        assert!(path.absolute);
+        assert_eq!(path.names, vec!["root".to_owned(), "Main".to_owned()]);
+        assert_eq!(path.subnames, vec!["position".to_owned(), "x".to_owned()]);
+        assert_eq!(path.to_string(), "/root/Main:position:x");
+
+        let relative: GodotNodePath = "../Player".parse().unwrap();
+
+        assert!(!relative.absolute);
+        assert_eq!(relative.to_string(), "../Player");
+    }
+}
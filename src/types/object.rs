@@ -0,0 +1,279 @@
+use std::hash::{Hash, Hasher};
+
+use super::{
+    encoded_string_length,
+    variant::{AsVariant, GodotVariant},
+    GodotTypeIndex, STRING_LENGTH_SIZE, TYPE_PADDING,
+};
+
+/// A resource id from godot. The id is only meaningful to the godot instance that created it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GodotRid {
+    pub id: u64,
+}
+
+impl GodotRid {
+    const BIT_SIZE: usize = 8;
+
+    pub fn new(id: u64) -> Self {
+        Self { id }
+    }
+
+    /// Whether the id points to a resource, an id of 0 is an invalid rid
+    pub fn is_valid(&self) -> bool {
+        self.id != 0
+    }
+}
+
+impl GodotVariant for GodotRid {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::BIT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::RID
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotRid>() {
+            self == other
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_u64(self.id);
+    }
+}
+
+/// A callable from godot. Godot can't serialize callables so it always sends a null callable with
+/// only the type header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GodotCallable;
+
+impl GodotVariant for GodotCallable {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Callable
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        other.as_var::<GodotCallable>().is_some()
+    }
+
+    fn variant_hash(&self, _: &mut dyn Hasher) {}
+}
+
+/// A signal from godot, the name of the signal and the id of the object that owns it. A null
+/// signal has an empty name and an object id of 0
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct GodotSignal {
+    pub name: String,
+    pub object_id: u64,
+}
+
+impl GodotSignal {
+    const OBJECT_ID_SIZE: usize = 8;
+
+    pub fn new(name: &str, object_id: u64) -> Self {
+        Self {
+            name: name.to_owned(),
+            object_id,
+        }
+    }
+
+    /// Whether the signal has no name or owner
+    pub fn is_null(&self) -> bool {
+        self.name.is_empty() && self.object_id == 0
+    }
+}
+
+impl GodotVariant for GodotSignal {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + encoded_string_length(&self.name) + Self::OBJECT_ID_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Signal
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotSignal>() {
+            self == other
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+/// An object from godot. Godot either sends an object as its instance id, or when using
+/// `var_to_bytes_with_objects` as its class name followed by each of its properties. A null object
+/// has no id and an empty class name
+#[derive(Debug, Default)]
+pub struct GodotObject {
+    /// The instance id of the object, this is only set when the object was sent as an id
+    pub id: Option<u64>,
+    pub class_name: String,
+    /// The properties of the object in the order they were sent
+    pub properties: Vec<(String, Box<dyn GodotVariant>)>,
+}
+
+impl GodotObject {
+    const ID_SIZE: usize = 8;
+    const PROPERTY_COUNT_SIZE: usize = 4;
+
+    /// Creates an object of a class with no properties
+    pub fn new(class_name: &str) -> Self {
+        Self {
+            id: None,
+            class_name: class_name.to_owned(),
+            properties: Vec::new(),
+        }
+    }
+
+    /// Creates an object that is only referenced by its instance id
+    pub fn from_id(id: u64) -> Self {
+        Self {
+            id: Some(id),
+            class_name: String::new(),
+            properties: Vec::new(),
+        }
+    }
+
+    /// Creates a null object
+    pub fn null() -> Self {
+        Self::default()
+    }
+
+    /// Whether the object is null, an object sent as an id of 0 is also null
+    pub fn is_null(&self) -> bool {
+        match self.id {
+            Some(id) => id == 0,
+            None => self.class_name.is_empty(),
+        }
+    }
+
+    /// Gets the value of a property if it exists and is the correct type
+    pub fn get<V>(&self, name: &str) -> Option<&V>
+    where
+        V: GodotVariant + 'static,
+    {
+        let (_, value) = self
+            .properties
+            .iter()
+            .find(|(property, _)| property == name)?;
+
+        value.as_var::<V>()
+    }
+
+    /// Sets the value of a property, an existing property keeps its position in the property list
+    pub fn set<V>(&mut self, name: &str, value: V)
+    where
+        V: GodotVariant + 'static,
+    {
+        let value = Box::new(value) as Box<dyn GodotVariant>;
+
+        if let Some((_, existing)) = self
+            .properties
+            .iter_mut()
+            .find(|(property, _)| property == name)
+        {
+            *existing = value;
+        } else {
+            self.properties.push((name.to_owned(), value));
+        }
+    }
+}
+
+impl GodotVariant for GodotObject {
+    fn byte_length(&self) -> usize {
+        if self.id.is_some() {
+            return TYPE_PADDING as usize + Self::ID_SIZE;
+        }
+
+        // A null object is only an empty class name
+        if self.class_name.is_empty() {
+            return TYPE_PADDING as usize + STRING_LENGTH_SIZE;
+        }
+
+        let properties_size: usize = self
+            .properties
+            .iter()
+            .map(|(name, value)| encoded_string_length(name) + value.byte_length())
+            .sum();
+
+        TYPE_PADDING as usize
+            + encoded_string_length(&self.class_name)
+            + Self::PROPERTY_COUNT_SIZE
+            + properties_size
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Object
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotObject>() {
+            self.id == other.id
+                && self.class_name == other.class_name
+                && self.properties.len() == other.properties.len()
+                && self
+                    .properties
+                    .iter()
+                    .zip(other.properties.iter())
+                    .all(|((name, value), (o_name, o_value))| name == o_name && value == o_value)
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.id.hash(&mut state);
+        self.class_name.hash(&mut state);
+        state.write_usize(self.properties.len());
+        for (name, value) in self.properties.iter() {
+            name.hash(&mut state);
+            value.hash(&mut state);
+        }
+    }
+}
@@ -0,0 +1,563 @@
+use std::hash::{Hash, Hasher};
+
+use super::{
+    eq_as_variant, hash_real, hash_reals, real_eq, real_size, reals_eq,
+    structures::{GodotColor, GodotVector2, GodotVector3, GodotVector4},
+    variant::{AsVariant, GodotVariant},
+    GodotTypeIndex, TYPE_PADDING,
+};
+
+/// The length prefix that comes after the header of every packed array
+const LENGTH_SIZE: usize = 4;
+
+/// A PackedByteArray from godot, a compact array of bytes
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotPackedByteArray {
+    pub values: Vec<u8>,
+}
+
+impl GodotPackedByteArray {
+    pub fn new(values: Vec<u8>) -> Self {
+        Self { values }
+    }
+}
+
+impl GodotVariant for GodotPackedByteArray {
+    fn byte_length(&self) -> usize {
+        let length = self.values.len();
+        // Pad 4 bytes because godot
+        let pad = (4 - (length % 4)) % 4;
+
+        TYPE_PADDING as usize + LENGTH_SIZE + length + pad
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedByteArray
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedByteArray>() {
+            self.values == other.values
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.values.hash(&mut state);
+    }
+}
+
+/// A PackedInt32Array from godot, a compact array of 32 bit integers
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotPackedInt32Array {
+    pub values: Vec<i32>,
+}
+
+impl GodotPackedInt32Array {
+    pub const ELEMENT_SIZE: usize = 4;
+
+    pub fn new(values: Vec<i32>) -> Self {
+        Self { values }
+    }
+}
+
+impl GodotVariant for GodotPackedInt32Array {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + LENGTH_SIZE + self.values.len() * Self::ELEMENT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedInt32Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedInt32Array>() {
+            self.values == other.values
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.values.hash(&mut state);
+    }
+}
+
+/// A PackedInt64Array from godot, a compact array of 64 bit integers
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotPackedInt64Array {
+    pub values: Vec<i64>,
+}
+
+impl GodotPackedInt64Array {
+    pub const ELEMENT_SIZE: usize = 8;
+
+    pub fn new(values: Vec<i64>) -> Self {
+        Self { values }
+    }
+}
+
+impl GodotVariant for GodotPackedInt64Array {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + LENGTH_SIZE + self.values.len() * Self::ELEMENT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedInt64Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedInt64Array>() {
+            self.values == other.values
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.values.hash(&mut state);
+    }
+}
+
+/// A PackedFloat32Array from godot, a compact array of 32 bit floats
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotPackedFloat32Array {
+    pub values: Vec<f32>,
+}
+
+impl GodotPackedFloat32Array {
+    pub const ELEMENT_SIZE: usize = 4;
+
+    pub fn new(values: Vec<f32>) -> Self {
+        Self { values }
+    }
+}
+
+impl GodotVariant for GodotPackedFloat32Array {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + LENGTH_SIZE + self.values.len() * Self::ELEMENT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedFloat32Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedFloat32Array>() {
+            self.values.len() == other.values.len()
+                && self
+                    .values
+                    .iter()
+                    .zip(other.values.iter())
+                    .all(|(value, o_value)| real_eq(*value as f64, *o_value as f64))
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.values.len());
+        for value in self.values.iter() {
+            hash_real(*value as f64, state);
+        }
+    }
+}
+
+/// A PackedFloat64Array from godot, a compact array of 64 bit floats
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotPackedFloat64Array {
+    pub values: Vec<f64>,
+}
+
+impl GodotPackedFloat64Array {
+    pub const ELEMENT_SIZE: usize = 8;
+
+    pub fn new(values: Vec<f64>) -> Self {
+        Self { values }
+    }
+}
+
+impl GodotVariant for GodotPackedFloat64Array {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + LENGTH_SIZE + self.values.len() * Self::ELEMENT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedFloat64Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedFloat64Array>() {
+            reals_eq(&self.values, &other.values)
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.values.len());
+        hash_reals(&self.values, state);
+    }
+}
+
+/// A PackedStringArray from godot, a compact array of strings
+#[derive(Debug, Clone, PartialEq)]
+pub struct GodotPackedStringArray {
+    pub values: Vec<String>,
+}
+
+impl GodotPackedStringArray {
+    pub fn new(values: Vec<String>) -> Self {
+        Self { values }
+    }
+}
+
+impl GodotVariant for GodotPackedStringArray {
+    fn byte_length(&self) -> usize {
+        let strings_size: usize = self
+            .values
+            .iter()
+            .map(|value| {
+                // Godot writes a null terminator after each string
+                let length = value.len() + 1;
+                // Pad 4 bytes because godot
+                let pad = (4 - (length % 4)) % 4;
+
+                LENGTH_SIZE + length + pad
+            })
+            .sum();
+
+        TYPE_PADDING as usize + LENGTH_SIZE + strings_size
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedStringArray
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedStringArray>() {
+            self.values == other.values
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.values.hash(&mut state);
+    }
+}
+
+/// A PackedVector2Array from godot, a compact array of Vector2s. Godot builds with double
+/// precision write each component as a 64 bit float
+#[derive(Debug, Clone)]
+pub struct GodotPackedVector2Array {
+    pub values: Vec<GodotVector2>,
+    /// Whether the components are written as 64 bit floats, the precision of each vector is
+    /// ignored
+    pub double_precision: bool,
+}
+
+impl GodotPackedVector2Array {
+    pub const COMPONENTS: usize = 2;
+
+    pub fn new(values: Vec<GodotVector2>) -> Self {
+        Self {
+            values,
+            double_precision: false,
+        }
+    }
+
+    /// The same array written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+}
+
+impl GodotVariant for GodotPackedVector2Array {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize
+            + LENGTH_SIZE
+            + self.values.len() * Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedVector2Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedVector2Array>() {
+            self.values.len() == other.values.len()
+                && self
+                    .values
+                    .iter()
+                    .zip(other.values.iter())
+                    .all(|(value, o_value)| reals_eq(&value.components(), &o_value.components()))
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.values.len());
+        for value in self.values.iter() {
+            hash_reals(&value.components(), state);
+        }
+    }
+}
+
+/// A PackedVector3Array from godot, a compact array of Vector3s. Godot builds with double
+/// precision write each component as a 64 bit float
+#[derive(Debug, Clone)]
+pub struct GodotPackedVector3Array {
+    pub values: Vec<GodotVector3>,
+    /// Whether the components are written as 64 bit floats, the precision of each vector is
+    /// ignored
+    pub double_precision: bool,
+}
+
+impl GodotPackedVector3Array {
+    pub const COMPONENTS: usize = 3;
+
+    pub fn new(values: Vec<GodotVector3>) -> Self {
+        Self {
+            values,
+            double_precision: false,
+        }
+    }
+
+    /// The same array written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+}
+
+impl GodotVariant for GodotPackedVector3Array {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize
+            + LENGTH_SIZE
+            + self.values.len() * Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedVector3Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedVector3Array>() {
+            self.values.len() == other.values.len()
+                && self
+                    .values
+                    .iter()
+                    .zip(other.values.iter())
+                    .all(|(value, o_value)| reals_eq(&value.components(), &o_value.components()))
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.values.len());
+        for value in self.values.iter() {
+            hash_reals(&value.components(), state);
+        }
+    }
+}
+
+/// A PackedVector4Array from godot, a compact array of Vector4s. Godot builds with double
+/// precision write each component as a 64 bit float
+#[derive(Debug, Clone)]
+pub struct GodotPackedVector4Array {
+    pub values: Vec<GodotVector4>,
+    /// Whether the components are written as 64 bit floats, the precision of each vector is
+    /// ignored
+    pub double_precision: bool,
+}
+
+impl GodotPackedVector4Array {
+    pub const COMPONENTS: usize = 4;
+
+    pub fn new(values: Vec<GodotVector4>) -> Self {
+        Self {
+            values,
+            double_precision: false,
+        }
+    }
+
+    /// The same array written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+}
+
+impl GodotVariant for GodotPackedVector4Array {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize
+            + LENGTH_SIZE
+            + self.values.len() * Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedVector4Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedVector4Array>() {
+            self.values.len() == other.values.len()
+                && self
+                    .values
+                    .iter()
+                    .zip(other.values.iter())
+                    .all(|(value, o_value)| reals_eq(&value.components(), &o_value.components()))
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.values.len());
+        for value in self.values.iter() {
+            hash_reals(&value.components(), state);
+        }
+    }
+}
+
+/// A PackedColorArray from godot, a compact array of Colors. Colors are always written as 32 bit
+/// floats
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotPackedColorArray {
+    pub values: Vec<GodotColor>,
+}
+
+impl GodotPackedColorArray {
+    pub const ELEMENT_SIZE: usize = 16;
+
+    pub fn new(values: Vec<GodotColor>) -> Self {
+        Self { values }
+    }
+}
+
+impl GodotVariant for GodotPackedColorArray {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + LENGTH_SIZE + self.values.len() * Self::ELEMENT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::PackedColorArray
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotPackedColorArray>() {
+            self.values.len() == other.values.len()
+                && self
+                    .values
+                    .iter()
+                    .zip(other.values.iter())
+                    .all(|(value, o_value)| reals_eq(&value.components(), &o_value.components()))
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_usize(self.values.len());
+        for value in self.values.iter() {
+            hash_reals(&value.components(), state);
+        }
+    }
+}
+
+eq_as_variant!(
+    GodotPackedVector2Array,
+    GodotPackedVector3Array,
+    GodotPackedVector4Array
+);
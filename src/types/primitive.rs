@@ -1,6 +1,10 @@
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 
-use super::{variant::{AsVariant, GodotVariant}, TYPE_PADDING};
+use super::{
+    encoded_string_length, hash_real, real_eq,
+    variant::{AsVariant, GodotVariant},
+    GodotTypeIndex, TYPE_PADDING,
+};
 
 /// Null Godot value
 #[derive(Debug)]
@@ -11,24 +15,31 @@ impl GodotVariant for GodotNull {
         TYPE_PADDING as usize
     }
 
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Nil
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
         other.as_var::<GodotNull>().is_some()
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        format!("{:?}", self).as_bytes().to_vec()
-    }
+    fn variant_hash(&self, _: &mut dyn Hasher) {}
 }
 
-/// An integer from godot
+/// An integer from godot, it is written as 64 bits when the value doesn't fit in 32 bits
 #[derive(Debug, Clone, Copy)]
 pub struct GodotInteger {
     pub value: i64,
-    pub byte_size: usize,
+    /// Whether the integer is written as 64 bits even when the value fits in 32 bits
+    pub force_64_bit: bool,
 }
 
 impl GodotInteger {
@@ -37,26 +48,52 @@ impl GodotInteger {
     pub fn new_from_i32(v: i32) -> Self {
         Self {
             value: v as i64,
-            byte_size: TYPE_PADDING as usize + Self::BIT_32_SIZE,
+            force_64_bit: false,
         }
     }
 
     pub fn new_from_i64(v: i64) -> Self {
         Self {
             value: v,
-            byte_size: TYPE_PADDING as usize + Self::BIT_64_SIZE,
+            force_64_bit: false,
+        }
+    }
+
+    /// An integer that is always written as 64 bits
+    pub fn new_64_bit(v: i64) -> Self {
+        Self {
+            value: v,
+            force_64_bit: true,
         }
     }
+
+    /// Whether the integer is encoded with 64 bits, either because it was asked for or because the
+    /// value needs it
+    pub fn is_64_bit(&self) -> bool {
+        self.force_64_bit || i32::try_from(self.value).is_err()
+    }
 }
 
 impl GodotVariant for GodotInteger {
     fn byte_length(&self) -> usize {
-        self.byte_size
+        if self.is_64_bit() {
+            TYPE_PADDING as usize + Self::BIT_64_SIZE
+        } else {
+            TYPE_PADDING as usize + Self::BIT_32_SIZE
+        }
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Integer
     }
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
         if let Some(other) = other.as_var::<GodotInteger>() {
             self.value == other.value
@@ -65,8 +102,8 @@ impl GodotVariant for GodotInteger {
         }
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        self.value.to_string().as_bytes().to_vec()
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_i64(self.value);
     }
 }
 
@@ -74,7 +111,8 @@ impl GodotVariant for GodotInteger {
 #[derive(Debug, Clone, Copy)]
 pub struct GodotFloat {
     pub value: f64,
-    pub byte_size: usize,
+    /// Whether the float is written as 64 bits
+    pub double_precision: bool,
 }
 
 impl GodotFloat {
@@ -83,37 +121,49 @@ impl GodotFloat {
     pub fn new_from_f32(v: f32) -> Self {
         Self {
             value: v as f64,
-            byte_size: TYPE_PADDING as usize + Self::BIT_32_SIZE,
+            double_precision: false,
         }
     }
 
     pub fn new_from_f64(v: f64) -> Self {
         Self {
             value: v,
-            byte_size: TYPE_PADDING as usize + Self::BIT_64_SIZE,
+            double_precision: true,
         }
     }
 }
 
 impl GodotVariant for GodotFloat {
     fn byte_length(&self) -> usize {
-        self.byte_size
+        if self.double_precision {
+            TYPE_PADDING as usize + Self::BIT_64_SIZE
+        } else {
+            TYPE_PADDING as usize + Self::BIT_32_SIZE
+        }
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Float
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
         if let Some(other) = other.as_var::<GodotFloat>() {
-            self.value == other.value
+            real_eq(self.value, other.value)
         } else {
             false
         }
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        self.value.to_string().as_bytes().to_vec()
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_real(self.value, state);
     }
 }
 
@@ -121,30 +171,33 @@ impl GodotVariant for GodotFloat {
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub struct GodotString {
     pub value: String,
-    pub byte_size: usize,
 }
 
 impl GodotString {
     pub fn new(s: &str) -> Self {
-        let length = s.len();
-        // Pad 4 bytes because godot
-        let pad = (4 - (length % 4)) % 4;
         Self {
             value: s.to_owned(),
-            byte_size: TYPE_PADDING as usize + pad + length,
         }
     }
 }
 
 impl GodotVariant for GodotString {
     fn byte_length(&self) -> usize {
-        self.byte_size
+        TYPE_PADDING as usize + encoded_string_length(&self.value)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::String
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
         if let Some(other) = other.as_var::<GodotString>() {
             self.value == other.value
@@ -153,8 +206,52 @@ impl GodotVariant for GodotString {
         }
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        self.value.as_bytes().to_vec()
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.value.hash(&mut state);
+    }
+}
+
+/// A StringName from godot, an interned string that is encoded the same way as a String
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct GodotStringName {
+    pub value: String,
+}
+
+impl GodotStringName {
+    pub fn new(s: &str) -> Self {
+        Self {
+            value: s.to_owned(),
+        }
+    }
+}
+
+impl GodotVariant for GodotStringName {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + encoded_string_length(&self.value)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::StringName
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotStringName>() {
+            self.value == other.value
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.value.hash(&mut state);
     }
 }
 
@@ -177,10 +274,18 @@ impl GodotVariant for GodotBool {
         TYPE_PADDING as usize + Self::BIT_SIZE
     }
 
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Bool
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
         if let Some(other) = other.as_var::<GodotBool>() {
             self.value == other.value
@@ -189,7 +294,7 @@ impl GodotVariant for GodotBool {
         }
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        format!("{:?}", self.value).as_bytes().to_vec()
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        state.write_u8(self.value as u8);
     }
 }
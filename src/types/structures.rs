@@ -1,79 +1,473 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
 use indexmap::IndexMap;
 
-use super::{variant::{AsVariant, GodotVariant}, TYPE_PADDING};
+use crate::error::{ConvertError, ParseColorError, TypeMismatchError};
 
-/// A Vector 2 from godot
-#[derive(PartialEq, Debug, Clone, Copy)]
+use super::{
+    convert::FromVariant,
+    eq_as_variant,
+    hash_reals,
+    primitive::{GodotNull, GodotString, GodotStringName},
+    real_size, reals_eq,
+    variant::{AsVariant, GodotVariant},
+    ContainerType, GodotTypeIndex, TYPE_PADDING,
+};
+
+/// A Vector 2 from godot. Godot builds with double precision write each component as a 64 bit
+/// float
+#[derive(Debug, Clone, Copy)]
 pub struct GodotVector2 {
-    pub x: f32,
-    pub y: f32,
+    pub x: f64,
+    pub y: f64,
+    /// Whether the components are written as 64 bit floats. This is only used when the vector is
+    /// encoded on its own, variants made up of vectors have their own precision
+    pub double_precision: bool,
 }
 
 impl GodotVector2 {
-    const BIT_SIZE: usize = 8;
-    pub fn new(x: f32, y: f32) -> Self {
-        Self { x, y }
+    const COMPONENTS: usize = 2;
+
+    pub fn new(x: f64, y: f64) -> Self {
+        Self {
+            x,
+            y,
+            double_precision: false,
+        }
+    }
+
+    /// The same vector written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them
+    pub fn components(&self) -> [f64; 2] {
+        [self.x, self.y]
     }
 }
 
 impl GodotVariant for GodotVector2 {
     fn byte_length(&self) -> usize {
-        TYPE_PADDING as usize + Self::BIT_SIZE
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Vector2
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
         if let Some(other) = other.as_var::<GodotVector2>() {
-            self.x == other.x && self.y == other.y
+            reals_eq(&self.components(), &other.components())
         } else {
             false
         }
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        format!("{}{}", self.x, self.y).as_bytes().to_vec()
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
     }
 }
 
-/// A Vector 3 from godot
-#[derive(PartialEq, Debug, Clone, Copy)]
+/// A Vector 3 from godot. Godot builds with double precision write each component as a 64 bit
+/// float
+#[derive(Debug, Clone, Copy)]
 pub struct GodotVector3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Whether the components are written as 64 bit floats. This is only used when the vector is
+    /// encoded on its own, variants made up of vectors have their own precision
+    pub double_precision: bool,
 }
 
 impl GodotVector3 {
+    const COMPONENTS: usize = 3;
+
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            double_precision: false,
+        }
+    }
+
+    /// The same vector written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them
+    pub fn components(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl GodotVariant for GodotVector3 {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Vector3
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotVector3>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Vector 2 of integers from godot
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct GodotVector2i {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl GodotVector2i {
+    const BIT_SIZE: usize = 8;
+
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl GodotVariant for GodotVector2i {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::BIT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Vector2I
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotVector2i>() {
+            self.x == other.x && self.y == other.y
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+/// A Vector 3 of integers from godot
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct GodotVector3i {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl GodotVector3i {
     const BIT_SIZE: usize = 12;
 
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
         Self { x, y, z }
     }
 }
 
-impl GodotVariant for GodotVector3 {
+impl GodotVariant for GodotVector3i {
     fn byte_length(&self) -> usize {
         TYPE_PADDING as usize + Self::BIT_SIZE
     }
 
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Vector3I
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
-        if let Some(other) = other.as_var::<GodotVector3>() {
+        if let Some(other) = other.as_var::<GodotVector3i>() {
             self.x == other.x && self.y == other.y && self.z == other.z
         } else {
             false
         }
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        format!("{}{}{}", self.x, self.y, self.y)
-            .as_bytes()
-            .to_vec()
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+/// A Vector 4 from godot. Godot builds with double precision write each component as a 64 bit
+/// float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotVector4 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+    /// Whether the components are written as 64 bit floats. This is only used when the vector is
+    /// encoded on its own, variants made up of vectors have their own precision
+    pub double_precision: bool,
+}
+
+impl GodotVector4 {
+    const COMPONENTS: usize = 4;
+
+    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            w,
+            double_precision: false,
+        }
+    }
+
+    /// The same vector written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them
+    pub fn components(&self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+}
+
+impl GodotVariant for GodotVector4 {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Vector4
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotVector4>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Vector 4 of integers from godot
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct GodotVector4i {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub w: i32,
+}
+
+impl GodotVector4i {
+    const BIT_SIZE: usize = 16;
+
+    pub fn new(x: i32, y: i32, z: i32, w: i32) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+impl GodotVariant for GodotVector4i {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::BIT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Vector4I
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotVector4i>() {
+            self.x == other.x && self.y == other.y && self.z == other.z && self.w == other.w
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+/// A Color from godot, each component is a float usually between 0 and 1
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct GodotColor {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl GodotColor {
+    const BIT_SIZE: usize = 16;
+
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Creates a color from 8 bit components between 0 and 255
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::new(
+            r as f32 / 255.0,
+            g as f32 / 255.0,
+            b as f32 / 255.0,
+            a as f32 / 255.0,
+        )
+    }
+
+    /// The color as 8 bit components between 0 and 255 in the order red, green, blue, alpha.
+    /// Components outside of 0 to 1 are clamped
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        [to_u8(self.r), to_u8(self.g), to_u8(self.b), to_u8(self.a)]
+    }
+
+    /// The components in the order godot writes them, red, green, blue then alpha
+    pub fn components(&self) -> [f64; 4] {
+        [self.r as f64, self.g as f64, self.b as f64, self.a as f64]
+    }
+
+    /// Creates a color from an HTML hex code such as `#ff8800` or `ff880080`. Like godot, the
+    /// `#` is optional and the code can be `RGB`, `RGBA`, `RRGGBB` or `RRGGBBAA`
+    pub fn from_html(html: &str) -> Result<Self, ParseColorError> {
+        let hex = html.strip_prefix('#').unwrap_or(html);
+        if !hex.is_ascii() {
+            return Err(ParseColorError(html.to_owned()));
+        }
+
+        let short = hex.len() <= 4;
+        let digits = if short { 1 } else { 2 };
+        let component = |i: usize| -> Result<u8, ParseColorError> {
+            let value = u8::from_str_radix(&hex[i * digits..(i + 1) * digits], 16)
+                .map_err(|_| ParseColorError(html.to_owned()))?;
+
+            // A single digit is repeated, so f becomes ff
+            Ok(if short { value * 17 } else { value })
+        };
+
+        match hex.len() {
+            3 | 6 => Ok(Self::from_rgba8(
+                component(0)?,
+                component(1)?,
+                component(2)?,
+                255,
+            )),
+            4 | 8 => Ok(Self::from_rgba8(
+                component(0)?,
+                component(1)?,
+                component(2)?,
+                component(3)?,
+            )),
+            _ => Err(ParseColorError(html.to_owned())),
+        }
+    }
+
+    /// The color as a lowercase HTML hex code without a `#`, like godot the alpha is written last
+    pub fn to_html(&self, with_alpha: bool) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+
+        if with_alpha {
+            format!("{r:02x}{g:02x}{b:02x}{a:02x}")
+        } else {
+            format!("{r:02x}{g:02x}{b:02x}")
+        }
+    }
+}
+
+impl GodotVariant for GodotColor {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::BIT_SIZE
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Color
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotColor>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
     }
 }
 
@@ -81,10 +475,16 @@ impl GodotVariant for GodotVector3 {
 #[derive(Debug)]
 pub struct GodotDictionary {
     pub map: IndexMap<Box<dyn GodotVariant>, Box<dyn GodotVariant>>,
-    pub byte_size: usize,
+    /// The type every key must be, for typed dictionaries such as `Dictionary[String, int]`
+    pub key_type: Option<ContainerType>,
+    /// The type every value must be, for typed dictionaries such as `Dictionary[String, int]`
+    pub value_type: Option<ContainerType>,
 }
 
 impl GodotDictionary {
+    /// The size of the entry count that comes before the entries
+    const LENGTH_SIZE: usize = 4;
+
     /// Gets a value from a dictionary
     pub fn get<V>(&self, key: impl GodotVariant + 'static) -> Option<&V>
     where
@@ -97,6 +497,30 @@ impl GodotDictionary {
         value.as_var::<V>()
     }
 
+    /// Gets a value by a string key. Godot 4 makes the keys of a dictionary written as
+    /// `{ key = value }` string names, so a string name with the same text is looked up too
+    pub fn get_by_name(&self, name: &str) -> Option<&dyn GodotVariant> {
+        let key = Box::new(GodotString::new(name)) as Box<dyn GodotVariant>;
+        if let Some(value) = self.map.get(&key) {
+            return Some(&**value);
+        }
+
+        let key = Box::new(GodotStringName::new(name)) as Box<dyn GodotVariant>;
+        self.map.get(&key).map(|value| &**value)
+    }
+
+    /// Reads a value by a string key into a rust type, such as `let hp: i32 = dict.get_as("hp")?`.
+    /// A missing key is read as null like `Dictionary.get` in godot, so it is only allowed when
+    /// the type is an [Option]
+    pub fn get_as<T: FromVariant>(&self, name: &str) -> Result<T, ConvertError> {
+        match self.get_by_name(name) {
+            Some(value) => T::from_variant(value).map_err(|error| error.in_field(name)),
+            None => {
+                T::from_variant(&GodotNull).map_err(|_| ConvertError::MissingField(name.to_owned()))
+            }
+        }
+    }
+
     /// Inserst a value into a dictionary
     pub fn insert<K, V>(&mut self, key: K, value: V)
     where
@@ -108,16 +532,61 @@ impl GodotDictionary {
         self.map.insert(key, value);
     }
 
+    /// Inserts a value into a dictionary. This will fail if the key or value does not match the
+    /// key or value type of a typed dictionary
+    pub fn try_insert<K, V>(&mut self, key: K, value: V) -> Result<(), TypeMismatchError>
+    where
+        K: GodotVariant + 'static,
+        V: GodotVariant + 'static,
+    {
+        self.validate_entry(&key, &value)?;
+        self.insert(key, value);
+
+        Ok(())
+    }
+
+    /// Checks that a key and value match the key and value type of the dictionary
+    pub fn validate_entry(
+        &self,
+        key: &dyn GodotVariant,
+        value: &dyn GodotVariant,
+    ) -> Result<(), TypeMismatchError> {
+        if let Some(key_type) = &self.key_type {
+            key_type.check(key)?;
+        }
+
+        if let Some(value_type) = &self.value_type {
+            value_type.check(value)?;
+        }
+
+        Ok(())
+    }
+
     /// Creates a dictionary that is empty
     pub fn new() -> Self {
         Self {
             map: IndexMap::new(),
-            byte_size: 0,
+            key_type: None,
+            value_type: None,
+        }
+    }
+
+    /// Creates a typed dictionary that is empty, a type of [None] means the keys or values can be
+    /// any type
+    pub fn new_typed(key_type: Option<ContainerType>, value_type: Option<ContainerType>) -> Self {
+        Self {
+            map: IndexMap::new(),
+            key_type,
+            value_type,
         }
     }
 
     pub fn new_from_map(map: IndexMap<Box<dyn GodotVariant>, Box<dyn GodotVariant>>) -> Self {
-        Self { map, byte_size: 0 }
+        Self {
+            map,
+            key_type: None,
+            value_type: None,
+        }
     }
 }
 
@@ -129,30 +598,355 @@ impl Default for GodotDictionary {
 
 impl GodotVariant for GodotDictionary {
     fn byte_length(&self) -> usize {
-        self.byte_size
+        let untyped = ContainerType::builtin(GodotTypeIndex::Nil);
+        let types_size = self.key_type.as_ref().unwrap_or(&untyped).encoded_length()
+            + self
+                .value_type
+                .as_ref()
+                .unwrap_or(&untyped)
+                .encoded_length();
+        let entries_size: usize = self
+            .map
+            .iter()
+            .map(|(key, value)| key.byte_length() + value.byte_length())
+            .sum();
+
+        TYPE_PADDING as usize + types_size + Self::LENGTH_SIZE + entries_size
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Dictionary
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
         if let Some(other) = other.as_var::<GodotDictionary>() {
-            for (key, value) in self.map.iter() {
-                for (o_key, o_value) in other.map.iter() {
-                    if key != o_key || value == o_value {
-                        return false;
-                    }
-                }
-            }
-
-            true
+            // Like godot the order of the entries doesn't matter, only that every key is in both
+            // and holds an equal value
+            self.map.len() == other.map.len()
+                && self
+                    .map
+                    .iter()
+                    .all(|(key, value)| other.map.get(key).is_some_and(|o_value| value == o_value))
         } else {
             false
         }
     }
 
-    fn bytes(&self) -> Vec<u8> {
-        format!("{:?}", self.map).as_bytes().to_vec()
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        // Dictionaries with the same entries in a different order are equal, so the hashes of the
+        // entries are combined in a way that doesn't depend on the order
+        let entries = self.map.iter().fold(0u64, |hash, (key, value)| {
+            let mut hasher = DefaultHasher::new();
+            key.hash(&mut hasher);
+            value.hash(&mut hasher);
+
+            hash.wrapping_add(hasher.finish())
+        });
+
+        state.write_usize(self.map.len());
+        state.write_u64(entries);
+    }
+}
+
+/// An Array from godot, it consists of an ordered list of values which can each be a different
+/// type
+#[derive(Debug)]
+pub struct GodotArray {
+    pub values: Vec<Box<dyn GodotVariant>>,
+}
+
+impl GodotArray {
+    /// The size of the value count that comes before the values
+    const LENGTH_SIZE: usize = 4;
+
+    /// Gets a value from the array at an index
+    pub fn get<V>(&self, index: usize) -> Option<&V>
+    where
+        V: GodotVariant + 'static,
+    {
+        let value = self.values.get(index)?;
+
+        value.as_var::<V>()
+    }
+
+    /// Pushes a value onto the end of the array
+    pub fn push<V>(&mut self, value: V)
+    where
+        V: GodotVariant + 'static,
+    {
+        self.values.push(Box::new(value));
+    }
+
+    /// The amount of values in the array
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the array has no values
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Creates an array that is empty
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    pub fn new_from_vec(values: Vec<Box<dyn GodotVariant>>) -> Self {
+        Self { values }
+    }
+}
+
+impl Default for GodotArray {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GodotVariant for GodotArray {
+    fn byte_length(&self) -> usize {
+        let values_size: usize = self.values.iter().map(|value| value.byte_length()).sum();
+
+        TYPE_PADDING as usize + GodotArray::LENGTH_SIZE + values_size
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        array_eq(&self.values, other)
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        array_hash(&self.values, state);
+    }
+}
+
+/// Whether the values are equal to the values of an array or typed array. Godot compares arrays by
+/// their values only, so an untyped array equals a typed array holding the same values
+fn array_eq(values: &[Box<dyn GodotVariant>], other: &dyn GodotVariant) -> bool {
+    if let Some(other) = other.as_var::<GodotArray>() {
+        values == other.values.as_slice()
+    } else if let Some(other) = other.as_var::<GodotTypedArray>() {
+        values == other.values.as_slice()
+    } else {
+        false
+    }
+}
+
+/// Hashes the values of an array or typed array, the element type is left out so that arrays that
+/// are equal through [array_eq] hash the same
+fn array_hash(values: &[Box<dyn GodotVariant>], mut state: &mut dyn Hasher) {
+    state.write_usize(values.len());
+    for value in values.iter() {
+        value.hash(&mut state);
+    }
+}
+
+/// A typed Array from godot such as `Array[int]`, every value in the array must match the
+/// element type of the array
+#[derive(Debug)]
+pub struct GodotTypedArray {
+    pub element_type: ContainerType,
+    pub values: Vec<Box<dyn GodotVariant>>,
+}
+
+impl GodotTypedArray {
+    /// Gets a value from the array at an index
+    pub fn get<V>(&self, index: usize) -> Option<&V>
+    where
+        V: GodotVariant + 'static,
+    {
+        let value = self.values.get(index)?;
+
+        value.as_var::<V>()
+    }
+
+    /// Pushes a value onto the end of the array. This will fail if the value does not match the
+    /// element type of the array
+    pub fn push<V>(&mut self, value: V) -> Result<(), TypeMismatchError>
+    where
+        V: GodotVariant + 'static,
+    {
+        self.element_type.check(&value)?;
+        self.values.push(Box::new(value));
+
+        Ok(())
+    }
+
+    /// The amount of values in the array
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the array has no values
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Creates a typed array that is empty
+    pub fn new(element_type: ContainerType) -> Self {
+        Self {
+            element_type,
+            values: Vec::new(),
+        }
+    }
+}
+
+impl GodotVariant for GodotTypedArray {
+    fn byte_length(&self) -> usize {
+        let values_size: usize = self.values.iter().map(|value| value.byte_length()).sum();
+
+        TYPE_PADDING as usize
+            + self.element_type.encoded_length()
+            + GodotArray::LENGTH_SIZE
+            + values_size
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Array
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        array_eq(&self.values, other)
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        array_hash(&self.values, state);
+    }
+}
+
+eq_as_variant!(GodotVector2, GodotVector3, GodotVector4);
+
+#[cfg(test)]
+mod tests {
+    use crate::types::primitive::{GodotFloat, GodotInteger, GodotString};
+
+    use crate::{error::ConvertError, types::variant::GodotVariant};
+
+    use super::{GodotDictionary, GodotVector3};
+
+    #[test]
+    fn dictionary_lookup() {
+        let mut dictionary = GodotDictionary::new();
+        dictionary.insert(GodotInteger::new_from_i32(1), GodotString::new("int"));
+        dictionary.insert(GodotString::new("1"), GodotString::new("string"));
+        dictionary.insert(GodotVector3::new(1.0, 2.0, 3.0), GodotString::new("z is 3"));
+        dictionary.insert(GodotVector3::new(1.0, 2.0, 2.0), GodotString::new("z is 2"));
+        dictionary.insert(GodotFloat::new_from_f64(f64::NAN), GodotString::new("nan"));
+
+        let lookups = [
+            (
+                dictionary.get::<GodotString>(GodotInteger::new_from_i64(1)),
+                "int",
+            ),
+            (
+                dictionary.get::<GodotString>(GodotString::new("1")),
+                "string",
+            ),
+            (
+                dictionary.get::<GodotString>(GodotVector3::new(1.0, 2.0, 3.0)),
+                "z is 3",
+            ),
+            (
+                dictionary.get::<GodotString>(GodotVector3::new(1.0, 2.0, 2.0)),
+                "z is 2",
+            ),
+            (
+                dictionary.get::<GodotString>(GodotFloat::new_from_f64(f64::NAN)),
+                "nan",
+            ),
+        ];
+
+        assert_eq!(dictionary.map.len(), 5);
+        for (value, expected) in lookups {
+            let value = value.map(|value| value.value.as_str());
+            assert_eq!(
+                Some(expected),
+                value,
+                "Expected {:?} but got {:?}",
+                expected,
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn dictionary_equality() {
+        let mut dictionary = GodotDictionary::new();
+        dictionary.insert(GodotString::new("hp"), GodotInteger::new_from_i32(100));
+        dictionary.insert(GodotString::new("name"), GodotString::new("player"));
+
+        let mut reordered = GodotDictionary::new();
+        reordered.insert(GodotString::new("name"), GodotString::new("player"));
+        reordered.insert(GodotString::new("hp"), GodotInteger::new_from_i32(100));
+
+        let mut changed = GodotDictionary::new();
+        changed.insert(GodotString::new("hp"), GodotInteger::new_from_i32(50));
+        changed.insert(GodotString::new("name"), GodotString::new("player"));
+
+        assert!(dictionary.variant_eq(&reordered));
+        assert!(!dictionary.variant_eq(&changed));
+        assert!(!dictionary.variant_eq(&GodotDictionary::new()));
+    }
+
+    #[test]
+    fn dictionary_get_as() {
+        let mut dictionary = GodotDictionary::new();
+        dictionary.insert(GodotString::new("hp"), GodotInteger::new_from_i32(100));
+        dictionary.insert(GodotString::new("speed"), GodotInteger::new_from_i32(2));
+
+        let hp: i32 = dictionary.get_as("hp").unwrap();
+        assert_eq!(100, hp, "Expected {:?} but got {:?}", 100, hp);
+
+        // Ints are promoted to floats like godot does
+        let speed: f64 = dictionary.get_as("speed").unwrap();
+        assert_eq!(2.0, speed, "Expected {:?} but got {:?}", 2.0, speed);
+
+        let missing: Option<i32> = dictionary.get_as("mana").unwrap();
+        assert_eq!(
+            None, missing,
+            "Expected {:?} but got {:?}",
+            None::<i32>, missing
+        );
+        assert_eq!(
+            Err(ConvertError::MissingField("mana".to_owned())),
+            dictionary.get_as::<i32>("mana")
+        );
+        assert!(dictionary.get_as::<String>("hp").is_err());
+    }
+
+    #[test]
+    fn precision_is_not_compared() {
+        let single = GodotVector3::new(1.0, 2.0, 3.0);
+        let double = single.with_double_precision();
+
+        assert_eq!(single, double, "Expected {:?} but got {:?}", single, double);
+        assert!(single.variant_eq(&double));
     }
 }
@@ -0,0 +1,465 @@
+use std::hash::Hasher;
+
+use super::{
+    eq_as_variant, hash_reals, real_size, reals_eq,
+    structures::{GodotVector2, GodotVector3, GodotVector4},
+    variant::{AsVariant, GodotVariant},
+    GodotTypeIndex, TYPE_PADDING,
+};
+
+/// A Transform2D from godot, a 2x3 matrix made up of the x axis, the y axis and the origin columns.
+/// Godot builds with double precision write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotTransform2D {
+    pub columns: [GodotVector2; 3],
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotTransform2D {
+    const COMPONENTS: usize = 6;
+
+    pub fn new(x: GodotVector2, y: GodotVector2, origin: GodotVector2) -> Self {
+        Self {
+            columns: [x, y, origin],
+            double_precision: false,
+        }
+    }
+
+    /// A transform with no rotation, scale or translation
+    pub fn identity() -> Self {
+        Self::new(
+            GodotVector2::new(1.0, 0.0),
+            GodotVector2::new(0.0, 1.0),
+            GodotVector2::new(0.0, 0.0),
+        )
+    }
+
+    /// The translation of the transform
+    pub fn origin(&self) -> GodotVector2 {
+        self.columns[2]
+    }
+
+    /// The same transform written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them, column by column
+    pub fn components(&self) -> [f64; 6] {
+        let [x, y, origin] = self.columns;
+
+        [x.x, x.y, y.x, y.y, origin.x, origin.y]
+    }
+}
+
+impl GodotVariant for GodotTransform2D {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Transform2D
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotTransform2D>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Basis from godot, a 3x3 matrix used for rotation and scale. Godot stores and writes the basis
+/// row by row, so the x axis is the first component of each row. Godot builds with double
+/// precision write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotBasis {
+    pub rows: [GodotVector3; 3],
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotBasis {
+    const COMPONENTS: usize = 9;
+
+    pub fn new(rows: [GodotVector3; 3]) -> Self {
+        Self {
+            rows,
+            double_precision: false,
+        }
+    }
+
+    /// A basis with no rotation or scale
+    pub fn identity() -> Self {
+        Self::new([
+            GodotVector3::new(1.0, 0.0, 0.0),
+            GodotVector3::new(0.0, 1.0, 0.0),
+            GodotVector3::new(0.0, 0.0, 1.0),
+        ])
+    }
+
+    /// Gets a column of the basis, column 0 is the x axis, 1 is the y axis and 2 is the z axis.
+    /// This panics if the index is more than 2, the same way indexing a slice does
+    pub fn column(&self, index: usize) -> GodotVector3 {
+        assert!(
+            index < 3,
+            "column index out of bounds: a basis has 3 columns but the index is {index}"
+        );
+
+        let component = |row: &GodotVector3| match index {
+            0 => row.x,
+            1 => row.y,
+            _ => row.z,
+        };
+
+        GodotVector3::new(
+            component(&self.rows[0]),
+            component(&self.rows[1]),
+            component(&self.rows[2]),
+        )
+    }
+
+    /// The same basis written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them, row by row
+    pub fn components(&self) -> [f64; 9] {
+        let [x, y, z] = self.rows;
+
+        [x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z]
+    }
+}
+
+impl GodotVariant for GodotBasis {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Basis
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotBasis>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Transform3D from godot, a 3x4 matrix made up of a basis and an origin. Godot builds with
+/// double precision write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotTransform3D {
+    pub basis: GodotBasis,
+    pub origin: GodotVector3,
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotTransform3D {
+    const COMPONENTS: usize = 12;
+
+    pub fn new(basis: GodotBasis, origin: GodotVector3) -> Self {
+        Self {
+            basis,
+            origin,
+            double_precision: false,
+        }
+    }
+
+    /// A transform with no rotation, scale or translation
+    pub fn identity() -> Self {
+        Self::new(GodotBasis::identity(), GodotVector3::new(0.0, 0.0, 0.0))
+    }
+
+    /// The same transform written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them, the basis rows followed by the origin
+    pub fn components(&self) -> [f64; 12] {
+        let mut components = [0.0; 12];
+        components[..9].copy_from_slice(&self.basis.components());
+        components[9..].copy_from_slice(&[self.origin.x, self.origin.y, self.origin.z]);
+
+        components
+    }
+}
+
+impl GodotVariant for GodotTransform3D {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Transform3D
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotTransform3D>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Projection from godot, a 4x4 matrix made up of 4 columns. Godot builds with double precision
+/// write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotProjection {
+    pub columns: [GodotVector4; 4],
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotProjection {
+    const COMPONENTS: usize = 16;
+
+    pub fn new(columns: [GodotVector4; 4]) -> Self {
+        Self {
+            columns,
+            double_precision: false,
+        }
+    }
+
+    /// A projection that does not change anything
+    pub fn identity() -> Self {
+        Self::new([
+            GodotVector4::new(1.0, 0.0, 0.0, 0.0),
+            GodotVector4::new(0.0, 1.0, 0.0, 0.0),
+            GodotVector4::new(0.0, 0.0, 1.0, 0.0),
+            GodotVector4::new(0.0, 0.0, 0.0, 1.0),
+        ])
+    }
+
+    /// The same projection written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them, column by column
+    pub fn components(&self) -> [f64; 16] {
+        let mut components = [0.0; 16];
+        for (i, column) in self.columns.iter().enumerate() {
+            components[i * 4..i * 4 + 4].copy_from_slice(&[column.x, column.y, column.z, column.w]);
+        }
+
+        components
+    }
+}
+
+impl GodotVariant for GodotProjection {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Projection
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotProjection>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+/// A Quaternion from godot, used to represent a rotation. Godot builds with double precision
+/// write each component as a 64 bit float
+#[derive(Debug, Clone, Copy)]
+pub struct GodotQuaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+    /// Whether the components are written as 64 bit floats
+    pub double_precision: bool,
+}
+
+impl GodotQuaternion {
+    const COMPONENTS: usize = 4;
+
+    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            w,
+            double_precision: false,
+        }
+    }
+
+    /// The same quaternion written with 64 bit floats, for godot builds with double precision
+    pub fn with_double_precision(self) -> Self {
+        Self {
+            double_precision: true,
+            ..self
+        }
+    }
+
+    /// The components in the order godot writes them
+    pub fn components(&self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// A quaternion with no rotation
+    pub fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// The length of the quaternion, a quaternion representing a rotation has a length of 1
+    pub fn length(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+    }
+
+    /// The quaternion scaled to a length of 1
+    pub fn normalized(&self) -> Self {
+        let length = self.length();
+
+        Self {
+            x: self.x / length,
+            y: self.y / length,
+            z: self.z / length,
+            w: self.w / length,
+            ..*self
+        }
+    }
+}
+
+impl GodotVariant for GodotQuaternion {
+    fn byte_length(&self) -> usize {
+        TYPE_PADDING as usize + Self::COMPONENTS * real_size(self.double_precision)
+    }
+
+    fn type_index(&self) -> GodotTypeIndex {
+        GodotTypeIndex::Quaternion
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+
+    fn variant_eq(&self, other: &dyn GodotVariant) -> bool {
+        if let Some(other) = other.as_var::<GodotQuaternion>() {
+            reals_eq(&self.components(), &other.components())
+        } else {
+            false
+        }
+    }
+
+    fn variant_hash(&self, state: &mut dyn Hasher) {
+        hash_reals(&self.components(), state);
+    }
+}
+
+eq_as_variant!(
+    GodotTransform2D,
+    GodotBasis,
+    GodotTransform3D,
+    GodotProjection,
+    GodotQuaternion
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::types::structures::GodotVector3;
+
+    use super::GodotBasis;
+
+    #[test]
+    fn basis_columns() {
+        let basis = GodotBasis::new([
+            GodotVector3::new(1.0, 2.0, 3.0),
+            GodotVector3::new(4.0, 5.0, 6.0),
+            GodotVector3::new(7.0, 8.0, 9.0),
+        ]);
+
+        let column = basis.column(2);
+        let expected = GodotVector3::new(3.0, 6.0, 9.0);
+        assert_eq!(
+            expected, column,
+            "Expected {:?} but got {:?}",
+            expected, column
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column index out of bounds")]
+    fn basis_column_out_of_bounds() {
+        GodotBasis::identity().column(3);
+    }
+}
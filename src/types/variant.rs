@@ -1,19 +1,62 @@
-use std::{fmt::Debug, hash::Hash};
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
+
+use crate::error::UnknownVariantError;
+
+use super::{
+    geometry::{GodotAabb, GodotPlane, GodotRect2, GodotRect2i},
+    node_path::GodotNodePath,
+    object::{GodotCallable, GodotObject, GodotRid, GodotSignal},
+    packed::{
+        GodotPackedByteArray, GodotPackedColorArray, GodotPackedFloat32Array,
+        GodotPackedFloat64Array, GodotPackedInt32Array, GodotPackedInt64Array,
+        GodotPackedStringArray, GodotPackedVector2Array, GodotPackedVector3Array,
+        GodotPackedVector4Array,
+    },
+    primitive::{GodotBool, GodotFloat, GodotInteger, GodotNull, GodotString, GodotStringName},
+    structures::{
+        GodotArray, GodotColor, GodotDictionary, GodotTypedArray, GodotVector2, GodotVector2i,
+        GodotVector3, GodotVector3i, GodotVector4, GodotVector4i,
+    },
+    transform::{GodotBasis, GodotProjection, GodotQuaternion, GodotTransform2D, GodotTransform3D},
+    GodotTypeIndex,
+};
+
+// The derive feature puts the derive next to the trait, it implements the conversions to and from
+// variants rather than the trait itself
+#[cfg(feature = "derive")]
+pub use godot_binary_serialization_derive::GodotVariant;
 
 /// Describes a godot variant
 pub trait GodotVariant: Debug + Send + Sync {
-    /// Describes the byte length of a variant, most primitive variants have a static byte length.
-    /// However some variants like a dictionary may have dynamic sizes
+    /// The amount of bytes the variant takes up once encoded for godot 4, most primitive variants
+    /// have a static byte length. However some variants like a dictionary are sized from the
+    /// values they hold. This is worked out from the current value so it is correct for variants
+    /// made by hand and after their fields are changed. The amount of bytes a variant took up
+    /// when it was decoded comes from
+    /// [decode_variant_consumed](crate::decoder::Decoder::decode_variant_consumed)
     fn byte_length(&self) -> usize;
 
+    /// The type index the variant is encoded with
+    fn type_index(&self) -> GodotTypeIndex;
+
     /// Allows us to downcast ref a variant for use
     fn as_any(&self) -> &dyn std::any::Any;
 
-    /// Checks if a variant is equal to another variant
+    /// Allows us to downcast a boxed variant to take ownership of it
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any>;
+
+    /// Checks if a variant is equal to another variant. Like godot, variants of different types
+    /// are never equal and floats that are both NaN are equal so a NaN key can still be found
+    /// in a dictionary
     fn variant_eq(&self, other: &dyn GodotVariant) -> bool;
 
-    /// The variant as a byte vector
-    fn bytes(&self) -> Vec<u8>;
+    /// Feeds the value of the variant into a hasher, variants that are equal through
+    /// [variant_eq](GodotVariant::variant_eq) must feed in the same data. The type index is
+    /// hashed separately so an integer and a string can't collide
+    fn variant_hash(&self, state: &mut dyn Hasher);
 }
 
 impl PartialEq for dyn GodotVariant {
@@ -24,9 +67,10 @@ impl PartialEq for dyn GodotVariant {
 
 impl Eq for dyn GodotVariant {}
 
-impl Hash for Box<dyn GodotVariant> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.bytes().hash(state)
+impl Hash for dyn GodotVariant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_index().hash(state);
+        self.variant_hash(state);
     }
 }
 
@@ -40,3 +84,249 @@ impl AsVariant for dyn GodotVariant + '_ {
         self.as_any().downcast_ref::<T>()
     }
 }
+
+/// Takes the value out of a boxed variant if it is of type T, otherwise the box is given back
+fn downcast<T>(variant: Box<dyn GodotVariant>) -> Result<T, Box<dyn GodotVariant>>
+where
+    T: GodotVariant + 'static,
+{
+    if !variant.as_any().is::<T>() {
+        return Err(variant);
+    }
+
+    match variant.into_any().downcast::<T>() {
+        Ok(value) => Ok(*value),
+        // The type was checked above
+        Err(_) => unreachable!(),
+    }
+}
+
+/// Defines [Variant] with a case for each godot type, along with the conversions between the cases
+/// and the types they hold
+macro_rules! variant_enum {
+    ($($case:ident($type:ty)),* $(,)?) => {
+        /// A godot variant as an enum with a case for every type, an alternative to
+        /// `Box<dyn GodotVariant>` that can be matched on. Containers still hold their values as
+        /// trait objects, which can be turned into a variant with [Variant::from_boxed].
+        ///
+        /// # Example
+        ///
+        /// ```rust,ignore
+        /// let variant = Variant::from_boxed(Decoder::decode_variant(&bytes)?)?;
+        ///
+        /// match variant {
+        ///     Variant::Integer(int) => println!("Got the integer {}", int.value),
+        ///     Variant::Dictionary(dict) => println!("Got {} entries", dict.map.len()),
+        ///     _ => {}
+        /// }
+        ///
+        /// let bytes = Encoder::encode_variant(variant.as_dyn())?;
+        /// ```
+        #[derive(Debug, Default)]
+        pub enum Variant {
+            #[default]
+            Nil,
+            $($case($type),)*
+        }
+
+        impl Variant {
+            /// The variant as a trait object, this is how it is passed to the encoder
+            pub fn as_dyn(&self) -> &dyn GodotVariant {
+                match self {
+                    Variant::Nil => &GodotNull,
+                    $(Variant::$case(value) => value,)*
+                }
+            }
+
+            /// Turns a boxed variant into the case for its type. This fails if the variant is a
+            /// type from outside of this crate, the variant is given back in the error
+            pub fn from_boxed(variant: Box<dyn GodotVariant>) -> Result<Self, UnknownVariantError> {
+                let variant = match downcast::<GodotNull>(variant) {
+                    Ok(_) => return Ok(Variant::Nil),
+                    Err(variant) => variant,
+                };
+
+                $(let variant = match downcast::<$type>(variant) {
+                    Ok(value) => return Ok(Variant::$case(value)),
+                    Err(variant) => variant,
+                };)*
+
+                Err(UnknownVariantError(variant))
+            }
+
+            /// Turns the variant into a boxed trait object without copying the value it holds
+            pub fn into_boxed(self) -> Box<dyn GodotVariant> {
+                match self {
+                    Variant::Nil => Box::new(GodotNull),
+                    $(Variant::$case(value) => Box::new(value),)*
+                }
+            }
+        }
+
+        impl From<GodotNull> for Variant {
+            fn from(_: GodotNull) -> Self {
+                Variant::Nil
+            }
+        }
+
+        $(impl From<$type> for Variant {
+            fn from(value: $type) -> Self {
+                Variant::$case(value)
+            }
+        })*
+    };
+}
+
+variant_enum! {
+    Bool(GodotBool),
+    Integer(GodotInteger),
+    Float(GodotFloat),
+    String(GodotString),
+    Vector2(GodotVector2),
+    Vector2I(GodotVector2i),
+    Rect2(GodotRect2),
+    Rect2I(GodotRect2i),
+    Vector3(GodotVector3),
+    Vector3I(GodotVector3i),
+    Transform2D(GodotTransform2D),
+    Vector4(GodotVector4),
+    Vector4I(GodotVector4i),
+    Plane(GodotPlane),
+    Quaternion(GodotQuaternion),
+    Aabb(GodotAabb),
+    Basis(GodotBasis),
+    Transform3D(GodotTransform3D),
+    Projection(GodotProjection),
+    Color(GodotColor),
+    StringName(GodotStringName),
+    NodePath(GodotNodePath),
+    RID(GodotRid),
+    Object(GodotObject),
+    Callable(GodotCallable),
+    Signal(GodotSignal),
+    Dictionary(GodotDictionary),
+    Array(GodotArray),
+    TypedArray(GodotTypedArray),
+    PackedByteArray(GodotPackedByteArray),
+    PackedInt32Array(GodotPackedInt32Array),
+    PackedInt64Array(GodotPackedInt64Array),
+    PackedFloat32Array(GodotPackedFloat32Array),
+    PackedFloat64Array(GodotPackedFloat64Array),
+    PackedStringArray(GodotPackedStringArray),
+    PackedVector2Array(GodotPackedVector2Array),
+    PackedVector3Array(GodotPackedVector3Array),
+    PackedColorArray(GodotPackedColorArray),
+    PackedVector4Array(GodotPackedVector4Array),
+}
+
+impl Variant {
+    /// The type index the variant is encoded with
+    pub fn type_index(&self) -> GodotTypeIndex {
+        self.as_dyn().type_index()
+    }
+
+    /// The amount of bytes the variant takes up once encoded for godot 4
+    pub fn byte_length(&self) -> usize {
+        self.as_dyn().byte_length()
+    }
+
+    /// Whether the variant is null
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Variant::Nil)
+    }
+}
+
+impl From<Variant> for Box<dyn GodotVariant> {
+    fn from(variant: Variant) -> Self {
+        variant.into_boxed()
+    }
+}
+
+impl TryFrom<Box<dyn GodotVariant>> for Variant {
+    type Error = UnknownVariantError;
+
+    fn try_from(variant: Box<dyn GodotVariant>) -> Result<Self, Self::Error> {
+        Self::from_boxed(variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::Hasher;
+
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        types::{
+            primitive::{GodotInteger, GodotNull, GodotString},
+            GodotTypeIndex,
+        },
+    };
+
+    use super::{GodotVariant, Variant};
+
+    /// A variant from outside of the crate
+    #[derive(Debug)]
+    struct Custom;
+
+    impl GodotVariant for Custom {
+        fn byte_length(&self) -> usize {
+            4
+        }
+
+        fn type_index(&self) -> GodotTypeIndex {
+            GodotTypeIndex::Nil
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+            self
+        }
+
+        fn variant_eq(&self, _: &dyn GodotVariant) -> bool {
+            false
+        }
+
+        fn variant_hash(&self, _: &mut dyn Hasher) {}
+    }
+
+    #[test]
+    fn variant_from_boxed() {
+        // { "hp": 100 }
+        let bytes = [
+            27, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 104, 112, 0, 0, 2, 0, 0, 0, 100, 0, 0,
+            0,
+        ];
+
+        let variant = Variant::from_boxed(Decoder::decode_variant(&bytes).unwrap()).unwrap();
+        let Variant::Dictionary(dict) = &variant else {
+            panic!("Expected a dictionary but got {:?}", variant);
+        };
+
+        let hp = dict.get::<GodotInteger>(GodotString::new("hp")).unwrap();
+        assert_eq!(hp.value, 100);
+
+        // Nothing is lost going back to a trait object
+        let encoded = Encoder::encode_variant(&*variant.into_boxed()).unwrap();
+        assert_eq!(encoded, bytes);
+    }
+
+    #[test]
+    fn variant_from_value() {
+        assert!(Variant::from(GodotNull).is_nil());
+        assert!(matches!(
+            Variant::from(GodotInteger::new_from_i32(3)),
+            Variant::Integer(GodotInteger { value: 3, .. })
+        ));
+        assert_eq!(
+            Variant::from(GodotString::new("hi")).type_index(),
+            GodotTypeIndex::String
+        );
+
+        let error = Variant::try_from(Box::new(Custom) as Box<dyn GodotVariant>).unwrap_err();
+        assert!(error.0.as_any().is::<Custom>());
+    }
+}
@@ -0,0 +1,85 @@
+//! Inputs that have crashed the decoder before. Every input and every truncation of it is passed to
+//! each decode entry point, none of them should panic. Inputs found by the fuzz targets in `fuzz/`
+//! should be added to `tests/corpus`
+
+use std::{fs, path::Path};
+
+use godot_binary_serialization::{
+    decoder::{DecodeLimits, DecodeOptions, Decoder},
+    encoder::Encoder,
+    types::ProtocolVersion,
+};
+
+#[path = "../fuzz/entry_points.rs"]
+mod entry_points;
+
+/// Parameters that between them choose every flag, container kind and option, and offsets at the
+/// start of the bytes and next to [usize::MAX]
+const PARAMS: [u8; 8] = [0, 1, 2, 3, 4, 7, 0x80, 0xFF];
+
+/// Decodes the bytes as a variant with each set of options, errors are fine but panics are not
+fn decode_variant(bytes: &[u8]) {
+    let options = [
+        DecodeOptions::default(),
+        DecodeOptions::with_objects(),
+        DecodeOptions::with_protocol(ProtocolVersion::Godot3),
+        DecodeOptions::with_limits(DecodeLimits {
+            max_depth: 4,
+            max_container_length: 16,
+            max_string_bytes: 64,
+            max_total_bytes: 1024,
+        }),
+    ];
+
+    for options in options.iter() {
+        if let Ok((variant, consumed)) =
+            Decoder::decode_variant_consumed_with_options(bytes, options)
+        {
+            assert!(
+                consumed <= bytes.len(),
+                "Decoded {} bytes from {} bytes",
+                consumed,
+                bytes.len()
+            );
+
+            // The computed length has to match what the variant encodes to
+            if let Ok(length) = Encoder::encoded_len(&*variant) {
+                assert_eq!(
+                    variant.byte_length(),
+                    length,
+                    "Wrong length for {:?}",
+                    variant
+                );
+            }
+        }
+    }
+}
+
+/// Calls every decode entry point with the bytes
+fn decode_all(bytes: &[u8]) {
+    for selector in 0..entry_points::ENTRY_POINTS {
+        for param in PARAMS {
+            entry_points::decode_entry_point(selector, param, bytes);
+        }
+    }
+}
+
+#[test]
+fn decode_corpus() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+
+    for entry in fs::read_dir(corpus).unwrap() {
+        let bytes = fs::read(entry.unwrap().path()).unwrap();
+
+        for end in 0..=bytes.len() {
+            decode_variant(&bytes[..end]);
+        }
+
+        // Large inputs are cut at evenly spaced points rather than at every byte when they are
+        // passed to every entry point, so that the test stays fast
+        let step = (bytes.len() / 256).max(1);
+        for end in (0..=bytes.len()).step_by(step).chain([bytes.len()]) {
+            decode_all(&bytes[..end]);
+        }
+    }
+}
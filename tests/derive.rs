@@ -0,0 +1,219 @@
+//! Structs converted to and from variants with the derive macros
+
+use godot_binary_serialization::{
+    decoder::Decoder,
+    error::ConvertError,
+    types::{
+        convert::{FromVariant, ToVariant},
+        packed::{GodotPackedInt32Array, GodotPackedInt64Array},
+        primitive::{GodotInteger, GodotString, GodotStringName},
+        structures::{GodotArray, GodotDictionary},
+        variant::{AsVariant, GodotVariant},
+    },
+};
+// The derive feature re-exports the macros next to the traits
+#[cfg(not(feature = "derive"))]
+use godot_binary_serialization_derive::{FromVariant, GodotVariant, ToVariant};
+
+#[derive(Debug, Clone, PartialEq, GodotVariant)]
+struct PlayerState {
+    hp: i32,
+    #[godot(rename = "display_name")]
+    name: String,
+    position: Position,
+    #[godot(int = 32)]
+    inventory: Vec<i64>,
+    #[godot(int = 64)]
+    ids: Vec<i64>,
+    target: Option<u32>,
+    #[godot(skip)]
+    dirty: bool,
+    #[godot(default = "default_level")]
+    level: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, GodotVariant)]
+struct Position(f32, f32);
+
+#[derive(Debug, Clone, PartialEq, ToVariant, FromVariant)]
+#[godot(tuple)]
+struct Move {
+    x: i32,
+    y: i32,
+    #[godot(default)]
+    run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, GodotVariant)]
+struct Score {
+    #[godot(int = 64)]
+    points: i64,
+    #[godot(int = 64)]
+    best: Option<i32>,
+}
+
+fn default_level() -> u8 {
+    1
+}
+
+fn player_state() -> PlayerState {
+    PlayerState {
+        hp: 100,
+        name: "player".to_owned(),
+        position: Position(1.5, -2.0),
+        inventory: vec![1, 2, 3],
+        ids: vec![i64::MAX],
+        target: None,
+        dirty: false,
+        level: 3,
+    }
+}
+
+#[test]
+fn struct_round_trip() {
+    let state = player_state();
+    let bytes = state.encode().unwrap();
+    let variant = Decoder::decode_variant(&bytes).unwrap();
+    let decoded = PlayerState::try_from(&*variant).unwrap();
+
+    assert_eq!(state, decoded, "Expected {:?} but got {:?}", state, decoded);
+}
+
+#[test]
+fn struct_layout() {
+    let variant = player_state().to_variant().unwrap();
+    let dictionary = variant.as_var::<GodotDictionary>().unwrap();
+
+    // Renamed and skipped fields
+    assert!(dictionary.get_by_name("name").is_none());
+    assert!(dictionary.get_by_name("dirty").is_none());
+    assert_eq!(
+        dictionary
+            .get::<GodotString>(GodotString::new("display_name"))
+            .map(|name| name.value.as_str()),
+        Some("player")
+    );
+
+    // Integer lists are sent with the width asked for
+    assert!(dictionary
+        .get::<GodotPackedInt32Array>(GodotString::new("inventory"))
+        .is_some());
+    assert!(dictionary
+        .get::<GodotPackedInt64Array>(GodotString::new("ids"))
+        .is_some());
+
+    // Tuple structs are arrays
+    let position = dictionary.get::<GodotArray>(GodotString::new("position"));
+    assert_eq!(position.map(|position| position.values.len()), Some(2));
+}
+
+#[test]
+fn decode_defaults_and_string_names() {
+    // Tuple structs are read from an array, missing fields with a default are filled in
+    let variant = GodotArray::new_from_vec(vec![
+        Box::new(GodotInteger::new_from_i32(1)),
+        Box::new(GodotInteger::new_from_i32(2)),
+    ]);
+    let decoded = Move::from_variant(&variant).unwrap();
+    let expected = Move {
+        x: 1,
+        y: 2,
+        run: false,
+    };
+    assert_eq!(
+        expected, decoded,
+        "Expected {:?} but got {:?}",
+        expected, decoded
+    );
+
+    // Keys written as `{ hp = 10 }` in godot are string names
+    let mut dictionary = player_state().to_variant().unwrap().into_any();
+    let dictionary = dictionary.downcast_mut::<GodotDictionary>().unwrap();
+    let hp = Box::new(GodotString::new("hp")) as Box<dyn GodotVariant>;
+    dictionary.map.shift_remove(&hp);
+    dictionary.insert(GodotStringName::new("hp"), GodotInteger::new_from_i32(10));
+    let level = Box::new(GodotString::new("level")) as Box<dyn GodotVariant>;
+    dictionary.map.shift_remove(&level);
+
+    let decoded = PlayerState::from_variant(&*dictionary).unwrap();
+    assert_eq!(decoded.hp, 10);
+    assert_eq!(decoded.level, default_level());
+}
+
+#[test]
+fn missing_option_is_none() {
+    // A missing key is read as null like `get_as` does, so optional fields don't have to be sent
+    let mut state = player_state();
+    state.target = Some(4);
+    let mut dictionary = state.to_variant().unwrap().into_any();
+    let dictionary = dictionary.downcast_mut::<GodotDictionary>().unwrap();
+    let target = Box::new(GodotString::new("target")) as Box<dyn GodotVariant>;
+    dictionary.map.shift_remove(&target);
+
+    let decoded = PlayerState::from_variant(&*dictionary).unwrap();
+    assert_eq!(
+        None, decoded.target,
+        "Expected {:?} but got {:?}",
+        None::<u32>, decoded.target
+    );
+}
+
+#[test]
+fn invalid_fields_fail() {
+    let mut dictionary = GodotDictionary::new();
+    dictionary.insert(GodotString::new("hp"), GodotString::new("full"));
+
+    let result = PlayerState::from_variant(&dictionary);
+    let expected = ConvertError::unexpected("an integer", &GodotString::new("full")).in_field("hp");
+    assert_eq!(
+        Err(expected.clone()),
+        result,
+        "Expected {:?} but got {:?}",
+        expected,
+        result
+    );
+
+    let mut dictionary = player_state().to_variant().unwrap().into_any();
+    let dictionary = dictionary.downcast_mut::<GodotDictionary>().unwrap();
+    let hp = Box::new(GodotString::new("hp")) as Box<dyn GodotVariant>;
+    dictionary.map.shift_remove(&hp);
+
+    let result = PlayerState::from_variant(&*dictionary);
+    let expected = ConvertError::MissingField("hp".to_owned());
+    assert_eq!(
+        Err(expected.clone()),
+        result,
+        "Expected {:?} but got {:?}",
+        expected,
+        result
+    );
+
+    // Integers have to fit in the width of the field
+    let mut state = player_state();
+    state.inventory.push(i64::MAX);
+    assert!(state.to_variant().is_err());
+}
+
+#[test]
+fn scalar_int_width() {
+    // Small integers are still sent with 64 bits when the field asks for it
+    let score = Score {
+        points: 7,
+        best: Some(3),
+    };
+    let variant = score.to_variant().unwrap();
+    let dictionary = variant.as_var::<GodotDictionary>().unwrap();
+
+    for field in ["points", "best"] {
+        let int = dictionary
+            .get::<GodotInteger>(GodotString::new(field))
+            .unwrap();
+        assert_eq!(int.byte_length(), 12, "Expected {} to be 64 bits", field);
+    }
+
+    let bytes = score.encode().unwrap();
+    assert_eq!(bytes.len(), variant.byte_length());
+
+    let decoded = Score::try_from(&*Decoder::decode_variant(&bytes).unwrap()).unwrap();
+    assert_eq!(score, decoded, "Expected {:?} but got {:?}", score, decoded);
+}
@@ -0,0 +1,183 @@
+//! Rust types mapped to variants with the serde feature
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use ::serde::{Deserialize, Serialize};
+use godot_binary_serialization::{
+    decoder::Decoder,
+    serde::{self, EnumRepresentation, SerializeOptions},
+    types::{
+        primitive::{GodotInteger, GodotNull, GodotString},
+        structures::{GodotArray, GodotDictionary, GodotVector2},
+        variant::{AsVariant, GodotVariant},
+    },
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Team {
+    Red,
+    Blue,
+    Custom { name: String, color: u32 },
+    Pair(i32, i32),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PlayerState {
+    hp: i32,
+    name: String,
+    position: (f32, f32),
+    inventory: Vec<String>,
+    target: Option<u64>,
+    team: Team,
+    stats: BTreeMap<String, f64>,
+}
+
+fn player_state() -> PlayerState {
+    PlayerState {
+        hp: 100,
+        name: "player".to_owned(),
+        position: (1.5, -2.0),
+        inventory: vec!["sword".to_owned(), "shield".to_owned()],
+        target: None,
+        team: Team::Custom {
+            name: "green".to_owned(),
+            color: 0x00ff00,
+        },
+        stats: BTreeMap::from([("speed".to_owned(), 2.5)]),
+    }
+}
+
+#[test]
+fn struct_round_trip() {
+    let state = player_state();
+    let bytes = serde::to_bytes(&state).unwrap();
+    let decoded: PlayerState = serde::from_bytes(&bytes).unwrap();
+
+    assert_eq!(state, decoded, "Expected {:?} but got {:?}", state, decoded);
+}
+
+#[test]
+fn struct_is_dictionary_with_string_keys() {
+    let bytes = serde::to_bytes(&player_state()).unwrap();
+    let variant = Decoder::decode_variant(&bytes).unwrap();
+    let dictionary = variant.as_var::<GodotDictionary>().unwrap();
+
+    let hp = dictionary.get::<GodotInteger>(GodotString::new("hp"));
+    assert_eq!(hp.map(|hp| hp.value), Some(100));
+
+    let target = dictionary
+        .map
+        .get(&(Box::new(GodotString::new("target")) as Box<dyn GodotVariant>));
+    assert!(target.is_some_and(|target| target.as_var::<GodotNull>().is_some()));
+
+    let inventory = dictionary.get::<GodotArray>(GodotString::new("inventory"));
+    assert_eq!(inventory.map(|inventory| inventory.values.len()), Some(2));
+}
+
+#[test]
+fn decode_from_godot() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Stats {
+        hp: u8,
+    }
+
+    // { "hp": 100 } from var_to_bytes
+    let bytes = [
+        27, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 104, 112, 0, 0, 2, 0, 0, 0, 100, 0, 0, 0,
+    ];
+    let stats: Stats = serde::from_bytes(&bytes).unwrap();
+    assert_eq!(stats, Stats { hp: 100 });
+
+    // Vectors are read as their components
+    let position: [f64; 2] = serde::from_variant(&GodotVector2::new(3.0, 4.0)).unwrap();
+    assert_eq!(position, [3.0, 4.0]);
+}
+
+#[test]
+fn enum_representations() {
+    let teams = [
+        Team::Blue,
+        Team::Custom {
+            name: "green".to_owned(),
+            color: 0x00ff00,
+        },
+        Team::Pair(1, 2),
+    ];
+
+    for enums in [EnumRepresentation::Name, EnumRepresentation::Index] {
+        let options = SerializeOptions::with_enums(enums);
+
+        for team in teams.iter() {
+            let bytes = serde::to_bytes_with_options(team, &options).unwrap();
+            let decoded: Team = serde::from_bytes(&bytes).unwrap();
+
+            assert_eq!(
+                *team, decoded,
+                "Expected {:?} but got {:?} with {:?}",
+                team, decoded, enums
+            );
+        }
+    }
+
+    // Unit variants are sent like godot sends enums
+    let index = serde::to_variant_with_options(
+        &Team::Blue,
+        &SerializeOptions::with_enums(EnumRepresentation::Index),
+    )
+    .unwrap();
+    assert_eq!(
+        index.as_var::<GodotInteger>().map(|index| index.value),
+        Some(1)
+    );
+
+    let name = serde::to_variant(&Team::Blue).unwrap();
+    assert_eq!(
+        name.as_var::<GodotString>().map(|name| name.value.as_str()),
+        Some("Blue")
+    );
+}
+
+#[test]
+fn enum_index_layout() {
+    let options = SerializeOptions::with_enums(EnumRepresentation::Index);
+
+    // Tuple variants are `[index, a, b]` so godot can match on them
+    let pair = serde::to_variant_with_options(&Team::Pair(1, 2), &options).unwrap();
+    let ints = pair
+        .as_var::<GodotArray>()
+        .unwrap()
+        .values
+        .iter()
+        .map(|value| value.as_var::<GodotInteger>().map(|int| int.value))
+        .collect::<Vec<_>>();
+    let expected = vec![Some(3), Some(1), Some(2)];
+    assert_eq!(expected, ints, "Expected {:?} but got {:?}", expected, ints);
+
+    // Struct variants are `[index, { "field": value }]`
+    let custom = Team::Custom {
+        name: "green".to_owned(),
+        color: 0x00ff00,
+    };
+    let custom = serde::to_variant_with_options(&custom, &options).unwrap();
+    let custom = custom.as_var::<GodotArray>().unwrap();
+    assert_eq!(custom.values.len(), 2);
+    assert_eq!(
+        custom.get::<GodotInteger>(0).map(|index| index.value),
+        Some(2)
+    );
+    assert_eq!(
+        custom
+            .get::<GodotDictionary>(1)
+            .map(|fields| fields.map.len()),
+        Some(2)
+    );
+}
+
+#[test]
+fn wrong_type_fails() {
+    let bytes = serde::to_bytes(&"not a number").unwrap();
+    let result = serde::from_bytes::<i32>(&bytes);
+
+    assert!(result.is_err(), "Expected an error but got {:?}", result);
+}
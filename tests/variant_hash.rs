@@ -0,0 +1,238 @@
+//! Variants are used as dictionary keys so equal variants must hash the same. Values are picked
+//! from a small pool so generated variants are often equal to each other
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use godot_binary_serialization::{
+    decoder::Decoder,
+    encoder::Encoder,
+    types::{
+        packed::{GodotPackedFloat32Array, GodotPackedVector3Array},
+        primitive::{GodotBool, GodotFloat, GodotInteger, GodotNull, GodotString},
+        structures::{
+            GodotArray, GodotDictionary, GodotTypedArray, GodotVector2, GodotVector3, GodotVector3i,
+        },
+        variant::GodotVariant,
+        ContainerType, GodotTypeIndex,
+    },
+};
+use proptest::prelude::*;
+
+/// A plain description of a variant, variants themselves can't be cloned which proptest needs
+#[derive(Debug, Clone)]
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Vector2(f64, f64),
+    Vector3(f64, f64, f64),
+    Vector3i(i32, i32, i32),
+    PackedFloat32(Vec<f32>),
+    PackedVector3(Vec<(f64, f64, f64)>),
+    Array(Vec<Value>),
+    Dictionary(Vec<(Value, Value)>),
+}
+
+impl Value {
+    fn variant(&self) -> Box<dyn GodotVariant> {
+        match self {
+            Value::Null => Box::new(GodotNull),
+            Value::Bool(value) => Box::new(GodotBool::new(*value)),
+            Value::Int(value) => Box::new(GodotInteger::new_from_i64(*value)),
+            Value::Float(value) => Box::new(GodotFloat::new_from_f64(*value)),
+            Value::String(value) => Box::new(GodotString::new(value)),
+            Value::Vector2(x, y) => Box::new(GodotVector2::new(*x, *y)),
+            Value::Vector3(x, y, z) => Box::new(GodotVector3::new(*x, *y, *z)),
+            Value::Vector3i(x, y, z) => Box::new(GodotVector3i::new(*x, *y, *z)),
+            Value::PackedFloat32(values) => Box::new(GodotPackedFloat32Array::new(values.clone())),
+            Value::PackedVector3(values) => Box::new(GodotPackedVector3Array::new(
+                values
+                    .iter()
+                    .map(|(x, y, z)| GodotVector3::new(*x, *y, *z))
+                    .collect(),
+            )),
+            Value::Array(values) => Box::new(GodotArray::new_from_vec(
+                values.iter().map(Value::variant).collect(),
+            )),
+            Value::Dictionary(entries) => {
+                let mut map = GodotDictionary::new().map;
+                for (key, value) in entries {
+                    map.insert(key.variant(), value.variant());
+                }
+
+                Box::new(GodotDictionary::new_from_map(map))
+            }
+        }
+    }
+
+    /// The same value with the sign of every zero and NaN flipped, godot treats these as equal
+    fn flip_signs(&self) -> Value {
+        let flip = |real: f64| {
+            if real == 0.0 || real.is_nan() {
+                -real
+            } else {
+                real
+            }
+        };
+
+        match self {
+            Value::Float(value) => Value::Float(flip(*value)),
+            Value::Vector2(x, y) => Value::Vector2(flip(*x), flip(*y)),
+            Value::Vector3(x, y, z) => Value::Vector3(flip(*x), flip(*y), flip(*z)),
+            Value::PackedFloat32(values) => Value::PackedFloat32(
+                values
+                    .iter()
+                    .map(|value| flip(*value as f64) as f32)
+                    .collect(),
+            ),
+            Value::PackedVector3(values) => Value::PackedVector3(
+                values
+                    .iter()
+                    .map(|(x, y, z)| (flip(*x), flip(*y), flip(*z)))
+                    .collect(),
+            ),
+            Value::Array(values) => Value::Array(values.iter().map(Value::flip_signs).collect()),
+            Value::Dictionary(entries) => Value::Dictionary(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.flip_signs(), value.flip_signs()))
+                    .collect(),
+            ),
+            value => value.clone(),
+        }
+    }
+}
+
+/// Reals that are exact as 32 bit floats, including the ones godot treats specially
+fn real() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        Just(0.0),
+        Just(-0.0),
+        Just(f64::NAN),
+        Just(-f64::NAN),
+        Just(f64::INFINITY),
+        (-2i8..=2).prop_map(|value| value as f64 / 2.0),
+    ]
+}
+
+fn value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        (-2i64..=2).prop_map(Value::Int),
+        real().prop_map(Value::Float),
+        prop::sample::select(vec!["", "1", "a"]).prop_map(|value| Value::String(value.into())),
+        (real(), real()).prop_map(|(x, y)| Value::Vector2(x, y)),
+        (real(), real(), real()).prop_map(|(x, y, z)| Value::Vector3(x, y, z)),
+        (0i32..=1, 0i32..=1, 0i32..=1).prop_map(|(x, y, z)| Value::Vector3i(x, y, z)),
+        prop::collection::vec(real().prop_map(|value| value as f32), 0..3)
+            .prop_map(Value::PackedFloat32),
+        prop::collection::vec((real(), real(), real()), 0..2).prop_map(Value::PackedVector3),
+    ];
+
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..3).prop_map(Value::Array),
+            prop::collection::vec((inner.clone(), inner), 0..3).prop_map(Value::Dictionary),
+        ]
+    })
+}
+
+fn hash(variant: &(dyn GodotVariant + 'static)) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    variant.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+proptest! {
+    #[test]
+    fn equal_variants_hash_the_same(a in value(), b in value()) {
+        let (a, b) = (a.variant(), b.variant());
+        if *a == *b {
+            prop_assert_eq!(hash(&*a), hash(&*b), "{:?} and {:?} are equal", a, b);
+        }
+    }
+
+    #[test]
+    fn variants_equal_themselves(value in value()) {
+        let (a, b) = (value.variant(), value.variant());
+        prop_assert!(*a == *b, "{:?} should equal itself", a);
+        prop_assert_eq!(hash(&*a), hash(&*b));
+    }
+
+    #[test]
+    fn signed_zeros_and_nans_are_equal(value in value()) {
+        let (a, b) = (value.variant(), value.flip_signs().variant());
+        prop_assert!(*a == *b, "Expected {:?} but got {:?}", a, b);
+        prop_assert_eq!(hash(&*a), hash(&*b));
+    }
+
+    #[test]
+    fn decoded_variants_equal_the_encoded_variant(value in value()) {
+        let variant = value.variant();
+        let decoded = Decoder::decode_variant(&Encoder::encode_variant(&*variant).unwrap()).unwrap();
+        prop_assert!(*variant == *decoded, "Expected {:?} but got {:?}", variant, decoded);
+        prop_assert_eq!(hash(&*variant), hash(&*decoded));
+    }
+
+    #[test]
+    fn dictionary_order_does_not_matter(entries in prop::collection::vec((value(), value()), 0..4)) {
+        let length = entries.len();
+        let forward = Value::Dictionary(entries.clone()).variant();
+        let backward = Value::Dictionary(entries.into_iter().rev().collect()).variant();
+
+        // A key that shows up twice keeps a different value depending on the order
+        let dictionary = forward.as_any().downcast_ref::<GodotDictionary>().unwrap();
+        prop_assume!(dictionary.map.len() == length);
+
+        prop_assert!(*forward == *backward, "Expected {:?} but got {:?}", forward, backward);
+        prop_assert_eq!(hash(&*forward), hash(&*backward));
+    }
+}
+
+#[test]
+fn typed_and_untyped_arrays_are_the_same_key() {
+    // Godot compares arrays by their values, `[1, 2] == Array([1, 2], TYPE_INT, "", null)`
+    let untyped = GodotArray::new_from_vec(vec![
+        Box::new(GodotInteger::new_from_i64(1)),
+        Box::new(GodotInteger::new_from_i64(2)),
+    ]);
+    let mut typed = GodotTypedArray::new(ContainerType::builtin(GodotTypeIndex::Integer));
+    typed.push(GodotInteger::new_from_i64(1)).unwrap();
+    typed.push(GodotInteger::new_from_i64(2)).unwrap();
+
+    let (untyped, typed): (Box<dyn GodotVariant>, Box<dyn GodotVariant>) =
+        (Box::new(untyped), Box::new(typed));
+    assert!(
+        *untyped == *typed,
+        "Expected {:?} but got {:?}",
+        untyped,
+        typed
+    );
+    assert!(
+        *typed == *untyped,
+        "Expected {:?} but got {:?}",
+        typed,
+        untyped
+    );
+    assert_eq!(hash(&*untyped), hash(&*typed));
+
+    // The typed array replaces the value of the untyped array rather than adding a second key
+    let mut dictionary = GodotDictionary::new();
+    dictionary
+        .map
+        .insert(untyped, Box::new(GodotString::new("untyped")));
+    dictionary
+        .map
+        .insert(typed, Box::new(GodotString::new("typed")));
+
+    assert_eq!(
+        dictionary.map.len(),
+        1,
+        "Expected one key but got {:?}",
+        dictionary
+    );
+}